@@ -15,6 +15,43 @@ fn type_override(id: &str) -> Option<String> {
 
         "aIRMDlauncher" | "eIRMDlauncher" => Some("IRMD".into()),
 
+        "ABomberAI" | "AV-42CAI" | "E-4" | "EF-24 AI" | "F-45A AI" | "FA-26B AI" | "KC-49"
+        | "MQ-31" | "T-55 AI" | "AEW-50" | "AIUCAV" | "ASF-30" | "ASF-33" | "ASF-58"
+        | "EBomberAI" | "GAV-25" | "T-55 AI-E" => Some("Aircraft".into()),
+
+        "alliedMBT1"
+        | "enemyMBT1"
+        | "AlliedIFV"
+        | "IFV-1"
+        | "APC"
+        | "EnemyAPC"
+        | "IRAPC"
+        | "SAAW"
+        | "AlliedSoldier"
+        | "AlliedSoldierMANPAD"
+        | "EnemySoldier"
+        | "EnemySoldierMANPAD"
+        | "ALogisticTruck"
+        | "ELogisticsTruck"
+        | "AJammerTruck"
+        | "EJammerTruck"
+        | "PhallanxTruck"
+        | "SRADTruck"
+        | "Artillery"
+        | "ARocketTruck"
+        | "ERocketTruck" => Some("GroundUnit".into()),
+
+        "AlliedAAShip"
+        | "DroneCarrier"
+        | "DroneGunBoat"
+        | "DroneGunBoatRocket"
+        | "DroneMissileCruiser"
+        | "ESuperMissileCruiser"
+        | "MineBoat" => Some("Ship".into()),
+
+        // Ships which aircraft can spawn on.
+        "AlliedCarrier" | "EnemyCarrier" | "EscortCruiser" => Some("Carrier".into()),
+
         _ => None,
     }
 }
@@ -22,9 +59,7 @@ fn type_override(id: &str) -> Option<String> {
 fn name_to_rust_identifier(name: &str) -> String {
     let name = name.replace(" ", "_");
     let name = name.replace("/", "_");
-    let name = name.replace("-", "_");
-
-    name
+    name.replace("-", "_")
 }
 
 fn main() {
@@ -25,6 +25,7 @@ pub struct Unit {
 fn generate_factions() -> TokenStream {
     quote! {
         #[derive(Debug, Clone, Copy, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize))]
         pub enum Faction {
             Allied,
             Enemy,
@@ -47,6 +48,7 @@ fn generate_unit_types(units: &[Unit]) -> TokenStream {
     quote! {
         #[allow(non_camel_case_types)]
         #[derive(Debug, Clone, Copy, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(::serde::Serialize))]
         pub enum UnitType {
             #(#types),*
         }
@@ -93,6 +95,28 @@ fn generate_fromstr_units(units: &[Unit]) -> TokenStream {
     }
 }
 
+fn generate_unit_ids(units: &[Unit]) -> TokenStream {
+    let identifiers = units
+        .iter()
+        .map(|u| &u.rust_identifier_name)
+        .map(|ident| proc_macro2::Ident::new(ident, proc_macro2::Span::call_site()));
+    let ids = units
+        .iter()
+        .map(|u| &u.id)
+        .map(|id| proc_macro2::Literal::string(id));
+
+    quote! {
+        impl Unit {
+            /// The `unitID` the game uses for this unit.
+            pub fn unit_id(&self) -> &'static str {
+                match self {
+                    #(Unit::#identifiers => #ids),*
+                }
+            }
+        }
+    }
+}
+
 fn generate_get_unit() -> TokenStream {
     quote! {
         pub fn get_unit(unit: &::vts_parsing::Node) -> Option<Unit> {
@@ -164,6 +188,8 @@ pub fn generate(from: impl AsRef<Path>) -> TokenStream {
 
     let unit_from_str = generate_fromstr_units(&parsed);
 
+    let unit_ids = generate_unit_ids(&parsed);
+
     let get_unit = generate_get_unit();
 
     let get_unit_type = generate_get_type(&parsed);
@@ -179,6 +205,8 @@ pub fn generate(from: impl AsRef<Path>) -> TokenStream {
 
         #unit_from_str
 
+        #unit_ids
+
         #get_unit
 
         #get_unit_type
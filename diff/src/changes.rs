@@ -0,0 +1,277 @@
+//! What changed between two versions of a scenario.
+//!
+//! Units are matched by `unitInstanceID`, units sharing an id by their order
+//! among the units with that id.
+
+use std::collections::{BTreeMap, HashMap};
+
+use vts_parsing::{Node, Value};
+use vts_units::fields::{parse_instance_id_list, IdList};
+use vts_units::write::format_value;
+use vts_units::{AnyUnit, Scenario, UnitRef, UnknownUnit};
+
+/// Both the units we know and those we don't, compared the same way.
+struct UnitView<'a> {
+    unit_id: &'a str,
+    name: &'a str,
+    position: Option<[f64; 3]>,
+    fields: &'a Node,
+}
+
+impl<'a> From<&'a UnitRef<'a>> for UnitView<'a> {
+    fn from(u: &'a UnitRef<'a>) -> Self {
+        UnitView {
+            unit_id: u.unit().unit_id(),
+            name: u.name(),
+            position: u.position(),
+            fields: u.fields(),
+        }
+    }
+}
+
+impl<'a> From<&'a UnknownUnit<'a>> for UnitView<'a> {
+    fn from(u: &'a UnknownUnit<'a>) -> Self {
+        UnitView {
+            unit_id: u.unit_id(),
+            name: u.name(),
+            position: u.position(),
+            fields: u.fields(),
+        }
+    }
+}
+
+impl<'a> From<&'a AnyUnit<'a>> for UnitView<'a> {
+    fn from(u: &'a AnyUnit<'a>) -> Self {
+        match u {
+            AnyUnit::Known(u) => u.into(),
+            AnyUnit::Unknown(u) => u.into(),
+        }
+    }
+}
+
+/// Which unit with an id, 0 for the first one and counting up for the
+/// [Scenario::duplicates].
+type UnitKey = (i64, usize);
+
+fn units<'a>(scenario: &'a Scenario) -> BTreeMap<UnitKey, UnitView<'a>> {
+    let known = scenario.units().values().map(|u| ((u.id(), 0), u.into()));
+    let unknown = scenario
+        .unknown_units()
+        .values()
+        .map(|u| ((u.id(), 0), u.into()));
+
+    let mut seen = HashMap::new();
+    let duplicates = scenario.duplicates().iter().map(|u| {
+        let occurrence = seen.entry(u.id()).or_insert(0);
+        *occurrence += 1;
+        ((u.id(), *occurrence), u.into())
+    });
+
+    known.chain(unknown).chain(duplicates).collect()
+}
+
+/// A unit as it's listed in the output.
+pub struct UnitSummary {
+    pub id: i64,
+    pub name: String,
+    pub unit_id: String,
+    /// Whether an earlier unit already has this id.
+    pub duplicate: bool,
+}
+
+impl UnitSummary {
+    fn new((id, occurrence): UnitKey, unit: &UnitView) -> UnitSummary {
+        UnitSummary {
+            id,
+            name: unit.name.to_string(),
+            unit_id: unit.unit_id.to_string(),
+            duplicate: occurrence > 0,
+        }
+    }
+}
+
+/// A change of one key, values are formatted like the game writes them.
+pub enum KeyChange {
+    Added {
+        key: String,
+        value: String,
+    },
+    Removed {
+        key: String,
+        value: String,
+    },
+    Changed {
+        key: String,
+        old: String,
+        new: String,
+    },
+    /// An instance id list, e.g. `radarUnits`.
+    Ids {
+        key: &'static str,
+        added: Vec<i64>,
+        removed: Vec<i64>,
+    },
+}
+
+pub struct Moved {
+    pub from: [f64; 3],
+    pub to: [f64; 3],
+    pub distance: f64,
+}
+
+pub struct UnitChanges {
+    pub unit: UnitSummary,
+    /// The old name, if it changed.
+    pub renamed_from: Option<String>,
+    /// The old `unitID`, if it changed.
+    pub unit_id_from: Option<String>,
+    pub moved: Option<Moved>,
+    /// Changes in `UnitFields`.
+    pub fields: Vec<KeyChange>,
+}
+
+pub struct ScenarioChanges {
+    /// Changes of the top-level values.
+    pub settings: Vec<KeyChange>,
+    pub added: Vec<UnitSummary>,
+    pub removed: Vec<UnitSummary>,
+    pub changed: Vec<UnitChanges>,
+}
+
+impl ScenarioChanges {
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// The id list a key is, if both versions of it can be read as one.
+fn id_list_change(key: &str, old: &Value, new: &Value) -> Option<KeyChange> {
+    let list = IdList::ALL.into_iter().find(|l| l.field() == key)?;
+    let field = list.field();
+
+    let old = parse_instance_id_list(old, field).ok()?;
+    let new = parse_instance_id_list(new, field).ok()?;
+
+    Some(KeyChange::Ids {
+        key: field,
+        added: new.iter().filter(|id| !old.contains(id)).copied().collect(),
+        removed: old.iter().filter(|id| !new.contains(id)).copied().collect(),
+    })
+}
+
+/// Changes between two sets of `key = value` pairs, in the order of `old`
+/// with added keys at the end.
+fn compare_values<'a>(
+    old: impl Iterator<Item = (&'a String, &'a Value)>,
+    new: impl Iterator<Item = (&'a String, &'a Value)>,
+) -> Vec<KeyChange> {
+    let old: Vec<_> = old.collect();
+    let new: Vec<_> = new.collect();
+    let find = |values: &[(&'a String, &'a Value)], key: &str| {
+        values.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    };
+
+    let mut changes = Vec::new();
+
+    for &(key, old_value) in &old {
+        match find(&new, key) {
+            None => changes.push(KeyChange::Removed {
+                key: key.clone(),
+                value: format_value(old_value),
+            }),
+            Some(new_value) if new_value != old_value => {
+                let change = id_list_change(key, old_value, new_value).unwrap_or_else(|| {
+                    KeyChange::Changed {
+                        key: key.clone(),
+                        old: format_value(old_value),
+                        new: format_value(new_value),
+                    }
+                });
+
+                // e.g. `1;2;` to `2;1;`, which is the same set.
+                if !matches!(&change, KeyChange::Ids { added, removed, .. } if added.is_empty() && removed.is_empty())
+                {
+                    changes.push(change);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    for &(key, new_value) in &new {
+        if find(&old, key).is_none() {
+            changes.push(KeyChange::Added {
+                key: key.clone(),
+                value: format_value(new_value),
+            });
+        }
+    }
+
+    changes
+}
+
+fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f64>()
+        .sqrt()
+}
+
+fn compare_unit(key: UnitKey, old: &UnitView, new: &UnitView) -> Option<UnitChanges> {
+    let moved = match (old.position, new.position) {
+        (Some(from), Some(to)) if from != to => Some(Moved {
+            from,
+            to,
+            distance: distance(from, to),
+        }),
+        _ => None,
+    };
+
+    let changes = UnitChanges {
+        unit: UnitSummary::new(key, new),
+        renamed_from: (old.name != new.name).then(|| old.name.to_string()),
+        unit_id_from: (old.unit_id != new.unit_id).then(|| old.unit_id.to_string()),
+        moved,
+        fields: compare_values(old.fields.values.iter(), new.fields.values.iter()),
+    };
+
+    let unchanged = changes.renamed_from.is_none()
+        && changes.unit_id_from.is_none()
+        && changes.moved.is_none()
+        && changes.fields.is_empty();
+
+    (!unchanged).then_some(changes)
+}
+
+pub fn compare(old: &Scenario, new: &Scenario) -> ScenarioChanges {
+    let old_units = units(old);
+    let new_units = units(new);
+
+    let removed = old_units
+        .iter()
+        .filter(|(key, _)| !new_units.contains_key(key))
+        .map(|(key, u)| UnitSummary::new(*key, u))
+        .collect();
+
+    let added = new_units
+        .iter()
+        .filter(|(key, _)| !old_units.contains_key(key))
+        .map(|(key, u)| UnitSummary::new(*key, u))
+        .collect();
+
+    let changed = new_units
+        .iter()
+        .filter_map(|(key, new)| compare_unit(*key, old_units.get(key)?, new))
+        .collect();
+
+    ScenarioChanges {
+        settings: compare_values(old.node().values.iter(), new.node().values.iter()),
+        added,
+        removed,
+        changed,
+    }
+}
@@ -0,0 +1,230 @@
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use anstyle::{AnsiColor, Style};
+use changes::{KeyChange, ScenarioChanges, UnitSummary};
+use clap::Parser;
+use vts_parsing::parse::try_parse;
+use vts_parsing::Node;
+use vts_units::Scenario;
+
+mod changes;
+
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  the scenarios are the same
+  1  the scenarios differ
+  2  invalid arguments, a scenario couldn't be read, parsed or converted, or
+     the output couldn't be written";
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "Compares two versions of a VTOL VR scenario unit by unit",
+    after_help = EXIT_CODES_HELP
+)]
+struct Args {
+    /// The old version of the scenario (.vts).
+    old: PathBuf,
+    /// The new version of the scenario (.vts).
+    new: PathBuf,
+}
+
+/// Reads and parses a scenario, the error is printable.
+fn load(path: &Path) -> Result<Node, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+
+    try_parse(&data).map_err(|e| format!("Couldn't parse {}:\n{e}", path.display()))
+}
+
+fn to_scenario<'a>(path: &Path, node: &'a Node) -> Result<Scenario<'a>, String> {
+    Scenario::from_node_lenient(node)
+        .map_err(|e| format!("Invalid scenario {}: {e}", path.display()))
+}
+
+struct Styles {
+    added: Style,
+    removed: Style,
+    changed: Style,
+    header: Style,
+}
+
+impl Styles {
+    fn new() -> Styles {
+        Styles {
+            added: Style::new().fg_color(Some(AnsiColor::Green.into())),
+            removed: Style::new().fg_color(Some(AnsiColor::Red.into())),
+            changed: Style::new().fg_color(Some(AnsiColor::Yellow.into())),
+            header: Style::new().bold(),
+        }
+    }
+}
+
+fn describe(unit: &UnitSummary) -> String {
+    let duplicate = if unit.duplicate { ", duplicate id" } else { "" };
+    format!(
+        "{} \"{}\" ({}){duplicate}",
+        unit.id, unit.name, unit.unit_id
+    )
+}
+
+fn format_position([x, y, z]: [f64; 3]) -> String {
+    format!("({x:.1}, {y:.1}, {z:.1})")
+}
+
+fn format_ids(ids: &[i64]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn print_key_changes(
+    out: &mut dyn Write,
+    styles: &Styles,
+    indent: &str,
+    changes: &[KeyChange],
+) -> io::Result<()> {
+    let Styles {
+        added,
+        removed,
+        changed,
+        ..
+    } = styles;
+
+    for change in changes {
+        match change {
+            KeyChange::Added { key, value } => {
+                writeln!(out, "{indent}{added}+ {key} = {value}{added:#}")?
+            }
+            KeyChange::Removed { key, value } => {
+                writeln!(out, "{indent}{removed}- {key} = {value}{removed:#}")?
+            }
+            KeyChange::Changed { key, old, new } => {
+                writeln!(out, "{indent}{changed}~ {key}: {old} -> {new}{changed:#}")?
+            }
+            KeyChange::Ids {
+                key,
+                added: new_ids,
+                removed: old_ids,
+            } => {
+                write!(out, "{indent}{changed}~ {key}:{changed:#}")?;
+                if !new_ids.is_empty() {
+                    write!(out, " {added}+{}{added:#}", format_ids(new_ids))?;
+                }
+                if !old_ids.is_empty() {
+                    write!(out, " {removed}-{}{removed:#}", format_ids(old_ids))?;
+                }
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_changes(out: &mut dyn Write, changes: &ScenarioChanges) -> io::Result<()> {
+    let styles = Styles::new();
+    let Styles {
+        added,
+        removed,
+        changed,
+        header,
+    } = &styles;
+
+    if changes.is_empty() {
+        writeln!(out, "No differences")?;
+        return Ok(());
+    }
+
+    if !changes.settings.is_empty() {
+        writeln!(out, "{header}Scenario{header:#}")?;
+        print_key_changes(out, &styles, "  ", &changes.settings)?;
+    }
+
+    if !changes.removed.is_empty() || !changes.added.is_empty() || !changes.changed.is_empty() {
+        writeln!(out, "{header}Units{header:#}")?;
+    }
+
+    for unit in &changes.removed {
+        writeln!(out, "  {removed}- {}{removed:#}", describe(unit))?;
+    }
+    for unit in &changes.added {
+        writeln!(out, "  {added}+ {}{added:#}", describe(unit))?;
+    }
+
+    for unit in &changes.changed {
+        writeln!(out, "  {changed}~ {}{changed:#}", describe(&unit.unit))?;
+
+        if let Some(ref old) = unit.unit_id_from {
+            writeln!(out, "    unitID: {old} -> {}", unit.unit.unit_id)?;
+        }
+        if let Some(ref old) = unit.renamed_from {
+            writeln!(out, "    unitName: {old} -> {}", unit.unit.name)?;
+        }
+        if let Some(ref moved) = unit.moved {
+            writeln!(
+                out,
+                "    moved {:.1}m: {} -> {}",
+                moved.distance,
+                format_position(moved.from),
+                format_position(moved.to)
+            )?;
+        }
+
+        print_key_changes(out, &styles, "    ", &unit.fields)?;
+    }
+
+    let summary = [
+        (changes.added.len(), "added"),
+        (changes.removed.len(), "removed"),
+        (changes.changed.len(), "changed"),
+    ];
+    let summary: Vec<_> = summary
+        .iter()
+        .map(|(count, what)| format!("{count} {what}"))
+        .collect();
+    writeln!(out, "Units: {}", summary.join(", "))?;
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let (old, new) = match (load(&args.old), load(&args.new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let scenarios =
+        to_scenario(&args.old, &old).and_then(|old| Ok((old, to_scenario(&args.new, &new)?)));
+    let (old, new) = match scenarios {
+        Ok(scenarios) => scenarios,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let changes = changes::compare(&old, &new);
+    let exit = match changes.is_empty() {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::from(1),
+    };
+
+    match print_changes(&mut anstream::stdout().lock(), &changes) {
+        Ok(()) => exit,
+        // e.g. `| head`, the reader didn't want the rest.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => exit,
+        Err(e) => {
+            eprintln!("Couldn't write output: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
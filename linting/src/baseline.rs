@@ -0,0 +1,219 @@
+//! Baselines, the known findings of legacy scenarios which shouldn't be
+//! reported until they're fixed.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "findings": [
+//!     {
+//!       "path": "campaign/mission1/mission1.vts",
+//!       "lint": "VTS0001",
+//!       "unit": 16,
+//!       "message": "MW has no Radars!"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Findings are matched by scenario, lint code, `unitInstanceID` and message,
+//! so they survive unrelated edits moving them to other lines. Paths are
+//! relative to the baseline file.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::lints::Warning;
+
+/// Version of the baseline layout, bumped on any breaking change.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+struct Entry {
+    path: String,
+    lint: String,
+    unit: Option<i64>,
+    message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawBaseline {
+    version: u32,
+    findings: Vec<Entry>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum BaselineError {
+    #[error("Couldn't read baseline {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        reason: std::io::Error,
+    },
+    #[error("Couldn't write baseline {}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        reason: std::io::Error,
+    },
+    #[error("Invalid baseline {}: {reason}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        reason: serde_json::Error,
+    },
+    #[error("Baseline {} has version {found}, expected {FORMAT_VERSION}", path.display())]
+    Version { path: PathBuf, found: u32 },
+}
+
+/// An absolute version of `path` without `.` and `..`, without touching the
+/// file system.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let mut normalized = PathBuf::new();
+
+    for component in std::path::absolute(path).ok()?.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            c => normalized.push(c),
+        }
+    }
+
+    Some(normalized)
+}
+
+/// `scenario` relative to the directory of the baseline at `baseline`, with
+/// `/` as separator so baselines work across platforms.
+fn relative_path(baseline: &Path, scenario: &Path) -> String {
+    let dir = baseline.parent().unwrap_or(Path::new(""));
+    let (Some(dir), Some(scenario)) = (normalize(dir), normalize(scenario)) else {
+        return scenario.display().to_string();
+    };
+
+    let relative = scenario.strip_prefix(&dir).unwrap_or(&scenario);
+
+    relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn entry(path: String, warning: &Warning) -> Entry {
+    Entry {
+        path,
+        lint: warning.lint.code.to_string(),
+        unit: warning.unit_id(),
+        message: warning.description.clone(),
+    }
+}
+
+/// Writes a baseline with all `findings`, returns how many there are.
+pub fn write(path: &Path, findings: &[(&Path, &[Warning])]) -> Result<usize, BaselineError> {
+    let mut entries: Vec<_> = findings
+        .iter()
+        .flat_map(|(scenario, warnings)| {
+            let scenario = relative_path(path, scenario);
+            warnings.iter().map(move |w| entry(scenario.clone(), w))
+        })
+        .collect();
+    entries.sort();
+
+    let count = entries.len();
+    let raw = RawBaseline {
+        version: FORMAT_VERSION,
+        findings: entries,
+    };
+
+    let mut data = serde_json::to_string_pretty(&raw).expect("baselines serialize");
+    data.push('\n');
+
+    std::fs::write(path, data).map_err(|e| BaselineError::Write {
+        path: path.to_path_buf(),
+        reason: e,
+    })?;
+
+    Ok(count)
+}
+
+/// A loaded baseline, tracking which of its findings still show up.
+pub struct Baseline {
+    path: PathBuf,
+    /// How many of each finding are left to match.
+    remaining: HashMap<Entry, usize>,
+    /// Scenarios we've seen, only their entries can be fixed.
+    seen: HashSet<String>,
+    hidden: usize,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, BaselineError> {
+        let data = std::fs::read_to_string(path).map_err(|e| BaselineError::Read {
+            path: path.to_path_buf(),
+            reason: e,
+        })?;
+
+        let raw: RawBaseline = serde_json::from_str(&data).map_err(|e| BaselineError::Parse {
+            path: path.to_path_buf(),
+            reason: e,
+        })?;
+
+        if raw.version != FORMAT_VERSION {
+            return Err(BaselineError::Version {
+                path: path.to_path_buf(),
+                found: raw.version,
+            });
+        }
+
+        let mut remaining = HashMap::new();
+        for entry in raw.findings {
+            *remaining.entry(entry).or_default() += 1;
+        }
+
+        Ok(Baseline {
+            path: path.to_path_buf(),
+            remaining,
+            seen: HashSet::new(),
+            hidden: 0,
+        })
+    }
+
+    /// Removes the warnings of `scenario` which are in the baseline.
+    pub fn filter(&mut self, scenario: &Path, warnings: &mut Vec<Warning>) {
+        let scenario = relative_path(&self.path, scenario);
+
+        warnings.retain(
+            |w| match self.remaining.get_mut(&entry(scenario.clone(), w)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    self.hidden += 1;
+                    false
+                }
+                _ => true,
+            },
+        );
+
+        self.seen.insert(scenario);
+    }
+
+    /// How many findings were removed by [Baseline::filter].
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// How many findings of the filtered scenarios are in the baseline, but
+    /// didn't show up anymore.
+    pub fn fixed(&self) -> usize {
+        self.remaining
+            .iter()
+            .filter(|(entry, _)| self.seen.contains(&entry.path))
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
@@ -0,0 +1,191 @@
+//! Checks across the scenarios of a campaign (`.vtc`).
+//!
+//! The findings are reported on the member scenarios, pointing at the
+//! offending key in their header.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use vts_units::campaign::Campaign;
+use vts_units::Scenario;
+
+use crate::lints::{Category, LintInfo, Severity, Warning};
+
+const DUPLICATE_ORDER_EXPLANATION: &str = "\
+The scenarios of a campaign are played in the order of their
+`campaignOrderIdx`. If two scenarios share an index, which one comes first
+isn't defined.
+
+This lint fires for every scenario whose index is already used by another
+scenario of the campaign.";
+
+pub const DUPLICATE_CAMPAIGN_ORDER: LintInfo = LintInfo {
+    code: "VTS0008",
+    name: "duplicate-campaign-order",
+    summary: "Every scenario of a campaign should have its own campaignOrderIdx.",
+    explanation: DUPLICATE_ORDER_EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
+
+const ORDER_GAP_EXPLANATION: &str = "\
+The scenarios of a campaign are played in the order of their
+`campaignOrderIdx`. A gap between two indices usually means a scenario was
+removed from the campaign, or was never added to it.
+
+This lint fires on the scenario after the gap.";
+
+pub const CAMPAIGN_ORDER_GAP: LintInfo = LintInfo {
+    code: "VTS0009",
+    name: "campaign-order-gap",
+    summary: "The campaignOrderIdx of a campaign's scenarios should have no gaps.",
+    explanation: ORDER_GAP_EXPLANATION,
+    default_severity: Severity::Warning,
+    category: Category::Suspicious,
+};
+
+const ID_MISMATCH_EXPLANATION: &str = "\
+Scenarios store the `campaignID` of the campaign they belong to. This lint
+fires if a scenario in the directory of a campaign names a different
+campaign, usually because it was copied from another campaign.";
+
+pub const CAMPAIGN_ID_MISMATCH: LintInfo = LintInfo {
+    code: "VTS0010",
+    name: "campaign-id-mismatch",
+    summary: "The campaignID of a scenario should match its campaign.",
+    explanation: ID_MISMATCH_EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
+
+const DUPLICATE_SCENARIO_EXPLANATION: &str = "\
+The game tells the scenarios of a campaign apart by their `scenarioID`. This
+lint fires for every scenario whose id is already used by another scenario of
+the campaign, usually because it was copied without changing it.";
+
+pub const DUPLICATE_SCENARIO_ID: LintInfo = LintInfo {
+    code: "VTS0011",
+    name: "duplicate-scenario-id",
+    summary: "Every scenario of a campaign should have its own scenarioID.",
+    explanation: DUPLICATE_SCENARIO_EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
+
+pub const RULES: [&LintInfo; 4] = [
+    &DUPLICATE_CAMPAIGN_ORDER,
+    &CAMPAIGN_ORDER_GAP,
+    &CAMPAIGN_ID_MISMATCH,
+    &DUPLICATE_SCENARIO_ID,
+];
+
+/// A scenario of a campaign.
+pub struct Member<'a> {
+    pub path: &'a Path,
+    pub scenario: &'a Scenario<'a>,
+}
+
+impl Member<'_> {
+    /// How other findings refer to this scenario.
+    fn describe(&self) -> String {
+        match self.scenario.scenario_id() {
+            Some(id) => format!("{id} ({})", self.path.display()),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// Runs the campaign checks, the findings are in the same order as `members`.
+pub fn check(campaign: &Campaign, members: &[Member]) -> Vec<Vec<Warning>> {
+    let mut warnings: Vec<Vec<Warning>> = members.iter().map(|_| Vec::new()).collect();
+
+    // index -> first member with it
+    let mut orders: BTreeMap<i64, usize> = BTreeMap::new();
+    let mut scenario_ids: HashMap<String, usize> = HashMap::new();
+
+    for (idx, member) in members.iter().enumerate() {
+        let scenario = member.scenario;
+
+        if let Some(order) = scenario.campaign_order_idx() {
+            match orders.get(&order) {
+                Some(&first) => warnings[idx].push(
+                    Warning::new(
+                        &DUPLICATE_CAMPAIGN_ORDER,
+                        None,
+                        format!(
+                            "campaignOrderIdx {order} is already used by {}.",
+                            members[first].describe()
+                        ),
+                    )
+                    .with_field("campaignOrderIdx"),
+                ),
+                None => {
+                    orders.insert(order, idx);
+                }
+            }
+        }
+
+        if let Some(id) = scenario.scenario_id() {
+            match scenario_ids.get(id.as_ref()) {
+                Some(&first) => warnings[idx].push(
+                    Warning::new(
+                        &DUPLICATE_SCENARIO_ID,
+                        None,
+                        format!(
+                            "scenarioID {id} is already used by {}.",
+                            members[first].path.display()
+                        ),
+                    )
+                    .with_field("scenarioID"),
+                ),
+                None => {
+                    scenario_ids.insert(id.into_owned(), idx);
+                }
+            }
+        }
+
+        let campaign_id = scenario.campaign_id();
+        if campaign_id.as_deref() != Some(campaign.id()) {
+            let found = match campaign_id {
+                Some(id) => format!("campaignID {id}"),
+                None => "No campaignID".to_string(),
+            };
+
+            warnings[idx].push(
+                Warning::new(
+                    &CAMPAIGN_ID_MISMATCH,
+                    None,
+                    format!("{found} doesn't match the campaign {}.", campaign.id()),
+                )
+                .with_field("campaignID"),
+            );
+        }
+    }
+
+    let orders: Vec<_> = orders.into_iter().collect();
+    for pair in orders.windows(2) {
+        let [(prev, _), (next, idx)] = pair else {
+            unreachable!("windows of 2");
+        };
+
+        if next - prev > 1 {
+            warnings[*idx].push(
+                Warning::new(
+                    &CAMPAIGN_ORDER_GAP,
+                    None,
+                    format!(
+                        "campaignOrderIdx jumps from {prev} to {next}, no scenario has {}.",
+                        if next - prev == 2 {
+                            (prev + 1).to_string()
+                        } else {
+                            format!("{} to {}", prev + 1, next - 1)
+                        }
+                    ),
+                )
+                .with_field("campaignOrderIdx"),
+            );
+        }
+    }
+
+    warnings
+}
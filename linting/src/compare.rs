@@ -0,0 +1,171 @@
+//! `--diff`, reporting only the findings a change introduced or resolved.
+//!
+//! Findings are matched across versions by lint, `unitInstanceID` and
+//! message first, then by lint and `unitInstanceID` alone, so a finding whose
+//! message changed (e.g. a distance) isn't reported as new.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use vts_linting::lints::Warning;
+
+/// What the scenarios are compared against.
+pub enum Old {
+    /// Another version of the one scenario.
+    File(PathBuf),
+    /// The version of each scenario at a git revision.
+    Revision(String),
+}
+
+impl Old {
+    /// A file if it exists, a git revision otherwise, the error is printable.
+    pub fn new(old: &str, scenarios: &[PathBuf]) -> Result<Old, String> {
+        if Path::new(old).is_file() {
+            return Ok(Old::File(PathBuf::from(old)));
+        }
+
+        let dir = scenarios.first().map_or(Path::new("."), |s| directory(s));
+        let verified = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--verify", "--quiet"])
+            .arg(format!("{old}^{{commit}}"))
+            .output()
+            .map_err(|e| format!("Couldn't run git: {e}"))?;
+
+        match verified.status.success() {
+            true => Ok(Old::Revision(old.to_string())),
+            false => Err(format!("{old} is neither a file nor a git revision")),
+        }
+    }
+
+    /// The old text of `scenario`, `None` if it didn't exist yet.
+    pub fn load(&self, scenario: &Path) -> Result<Option<String>, String> {
+        match self {
+            Old::File(path) => std::fs::read_to_string(path)
+                .map(Some)
+                .map_err(|e| format!("Couldn't read {}: {e}", path.display())),
+            Old::Revision(revision) => git_show(revision, scenario),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Old::File(path) => path.display().to_string(),
+            Old::Revision(revision) => revision.clone(),
+        }
+    }
+}
+
+/// The directory of `path`, `.` for a bare file name, whose parent is empty.
+pub fn directory(path: &Path) -> &Path {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// `git show revision:path`, `None` if the file isn't in that revision.
+fn git_show(revision: &str, path: &Path) -> Result<Option<String>, String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("{} isn't a file", path.display()))?;
+    // `./` makes git look the path up relative to the directory we run in.
+    let spec = format!("{revision}:./{}", name.to_string_lossy());
+
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(directory(path))
+            .args(args)
+            .arg(&spec)
+            .output()
+            .map_err(|e| format!("Couldn't run git: {e}"))
+    };
+
+    if !git(&["cat-file", "-e"])?.status.success() {
+        return Ok(None);
+    }
+
+    let shown = git(&["show"])?;
+    if !shown.status.success() {
+        return Err(format!(
+            "Couldn't get {spec}: {}",
+            String::from_utf8_lossy(&shown.stderr).trim()
+        ));
+    }
+
+    String::from_utf8(shown.stdout)
+        .map(Some)
+        .map_err(|_| format!("{spec} isn't valid UTF-8"))
+}
+
+/// The findings resolved since the old version, by scenario.
+pub type Resolved = Vec<(PathBuf, Vec<Warning>)>;
+
+type Key = (&'static str, Option<i64>);
+
+/// Splits findings into those only in `new` and those only in `old`.
+pub fn changes(old: Vec<Warning>, new: Vec<Warning>) -> (Vec<Warning>, Vec<Warning>) {
+    let mut old: Vec<Option<Warning>> = old.into_iter().map(Some).collect();
+    let mut introduced = Vec::new();
+
+    // Exact matches first, so similar findings on one unit pair up right.
+    let mut unmatched = Vec::new();
+    for w in new {
+        let exact = old.iter().position(|o| {
+            o.as_ref().is_some_and(|o| {
+                o.lint == w.lint && o.unit_id() == w.unit_id() && o.description == w.description
+            })
+        });
+
+        match exact {
+            Some(idx) => old[idx] = None,
+            None => unmatched.push(w),
+        }
+    }
+
+    let mut by_key: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (idx, o) in old.iter().enumerate() {
+        if let Some(o) = o {
+            by_key
+                .entry((o.lint.code, o.unit_id()))
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    for w in unmatched {
+        match by_key
+            .get_mut(&(w.lint.code, w.unit_id()))
+            .and_then(|v| v.pop())
+        {
+            Some(idx) => old[idx] = None,
+            None => introduced.push(w),
+        }
+    }
+
+    (introduced, old.into_iter().flatten().collect())
+}
+
+/// Lists findings which a change resolved.
+pub fn print_resolved(out: &mut dyn Write, old: &Old, resolved: &Resolved) -> io::Result<()> {
+    let count: usize = resolved.iter().map(|(_, r)| r.len()).sum();
+    writeln!(out, "{count} findings resolved since {}", old.describe())?;
+
+    for (path, warnings) in resolved {
+        for w in warnings {
+            writeln!(
+                out,
+                "- {}[{}] {}: {w}",
+                w.severity.as_str(),
+                w.lint.code,
+                path.display()
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+//! `vts-lint.toml`, the per-repository configuration of the linter.
+//!
+//! ```toml
+//! # used if --format isn't given, configs used together can't disagree
+//! format = "github"
+//! # regexes, findings on units with a matching unitName are dropped
+//! ignore-units = ["^Decoy "]
+//!
+//! [lints]
+//! # "allow" disables a lint, a severity enables it with that severity
+//! mw-has-radar = "allow"
+//! VTS0002 = "error"
+//! ```
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::lints::{find_lint, LintInfo, Severity, Warning};
+use crate::report::Format;
+
+pub const FILE_NAME: &str = "vts-lint.toml";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Help,
+    Note,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+struct RawConfig {
+    format: Option<Format>,
+    ignore_units: Vec<String>,
+    lints: HashMap<String, LintLevel>,
+}
+
+#[derive(Debug, Default)]
+pub struct Config {
+    pub format: Option<Format>,
+    pub ignore_units: Vec<Regex>,
+    pub lints: Vec<(&'static LintInfo, LintLevel)>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+    #[error("Couldn't read {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        reason: std::io::Error,
+    },
+    #[error("Invalid config {}: {reason}", path.display())]
+    Parse {
+        path: PathBuf,
+        #[source]
+        reason: toml::de::Error,
+    },
+    #[error("Unknown lint {name} in {}, see --list-lints", path.display())]
+    UnknownLint { path: PathBuf, name: String },
+    #[error(
+        "{} and {} set different formats, choose one with --format",
+        first.display(),
+        second.display()
+    )]
+    ConflictingFormat { first: PathBuf, second: PathBuf },
+    #[error("Invalid pattern {pattern} in ignore-units of {}: {reason}", path.display())]
+    InvalidPattern {
+        path: PathBuf,
+        pattern: String,
+        #[source]
+        reason: regex::Error,
+    },
+}
+
+impl Config {
+    /// Finds the closest `vts-lint.toml` in the directory of the scenario
+    /// or any of its parents.
+    pub fn discover(scenario: &Path) -> Option<PathBuf> {
+        let scenario = std::path::absolute(scenario).ok()?;
+
+        scenario
+            .ancestors()
+            .skip(1)
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|p| p.is_file())
+    }
+
+    /// Loads and validates a config, lint names are checked against `rules`.
+    pub fn load(path: &Path, rules: &[&'static LintInfo]) -> Result<Config, ConfigError> {
+        let data = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.to_path_buf(),
+            reason: e,
+        })?;
+
+        let raw: RawConfig = toml::from_str(&data).map_err(|e| ConfigError::Parse {
+            path: path.to_path_buf(),
+            reason: e,
+        })?;
+
+        let ignore_units = raw
+            .ignore_units
+            .into_iter()
+            .map(|p| {
+                Regex::new(&p).map_err(|e| ConfigError::InvalidPattern {
+                    path: path.to_path_buf(),
+                    pattern: p,
+                    reason: e,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        let lints = raw
+            .lints
+            .into_iter()
+            .map(|(name, level)| match find_lint(rules, &name) {
+                Some(lint) => Ok((lint, level)),
+                None => Err(ConfigError::UnknownLint {
+                    path: path.to_path_buf(),
+                    name,
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Config {
+            format: raw.format,
+            ignore_units,
+            lints,
+        })
+    }
+
+    /// Whether the config turns this lint off.
+    pub fn is_allowed(&self, lint: &LintInfo) -> bool {
+        self.lints
+            .iter()
+            .any(|(l, level)| *l == lint && matches!(level, LintLevel::Allow))
+    }
+
+    /// Applies severity overrides and drops findings on ignored units.
+    pub fn apply(&self, warnings: &mut Vec<Warning>) {
+        warnings.retain(|w| {
+            w.unit_name()
+                .is_none_or(|name| !self.ignore_units.iter().any(|p| p.is_match(name)))
+        });
+
+        for w in warnings {
+            let level = self
+                .lints
+                .iter()
+                .find(|(l, _)| *l == w.lint)
+                .map(|(_, level)| *level);
+
+            w.severity = match level {
+                Some(LintLevel::Help) => Severity::Help,
+                Some(LintLevel::Note) => Severity::Note,
+                Some(LintLevel::Warning) => Severity::Warning,
+                Some(LintLevel::Error) => Severity::Error,
+                Some(LintLevel::Allow) | None => continue,
+            };
+        }
+    }
+}
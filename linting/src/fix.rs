@@ -0,0 +1,96 @@
+//! Applying the suggested [Fix](crate::lints::Fix)es of warnings to the
+//! scenario text.
+//!
+//! Edits only touch the `key = value` lines they're about, everything else
+//! stays byte for byte the same.
+
+use std::collections::BTreeMap;
+
+use crate::lints::{Edit, Warning};
+use crate::source::SourceMap;
+
+/// The scenario text after applying fixes.
+pub struct Fixed {
+    pub text: String,
+    /// How many warnings were fixed.
+    pub fixed: usize,
+}
+
+/// Applies `edits` to the value of a `key = value` line.
+fn edit_line(line: &str, edits: &[&Edit]) -> String {
+    let (key, value) = line.split_once('=').unwrap_or((line, ""));
+    let key = key.trim();
+    let mut value = value.trim().to_string();
+
+    for edit in edits {
+        match edit {
+            Edit::Set { value: new, .. } => value = new.clone(),
+            Edit::RemoveId { id, .. } => {
+                value = value
+                    .split(';')
+                    .filter(|v| !v.is_empty() && v.parse() != Ok(*id))
+                    .map(|v| format!("{v};"))
+                    .collect();
+            }
+        }
+    }
+
+    // The game writes `key = ` for empty values, so we do too.
+    format!("{key} = {value}")
+}
+
+/// Applies the fixes of `warnings` to `source`. Fixes whose lines can't be
+/// found are skipped.
+pub fn apply(source: &str, warnings: &[Warning]) -> Fixed {
+    let map = SourceMap::new(source);
+
+    // line start -> (line end, edits)
+    let mut lines: BTreeMap<usize, (usize, Vec<&Edit>)> = BTreeMap::new();
+    let mut fixed = 0;
+
+    for w in warnings {
+        let Some(ref fix) = w.fix else {
+            continue;
+        };
+
+        let spans: Option<Vec<_>> = fix
+            .edits
+            .iter()
+            .map(|e| {
+                let field = match e {
+                    Edit::Set { field, .. } | Edit::RemoveId { field, .. } => field,
+                };
+                map.field(w, field).map(|span| (span, e))
+            })
+            .collect();
+
+        let Some(spans) = spans else {
+            continue;
+        };
+
+        for (span, edit) in spans {
+            // Include trailing spaces, e.g. of `radarUnits = `.
+            let rest = &source[span.end..];
+            let end = span.end + (rest.len() - rest.trim_start_matches([' ', '\t']).len());
+
+            lines
+                .entry(span.start)
+                .or_insert((end, vec![]))
+                .1
+                .push(edit);
+        }
+        fixed += 1;
+    }
+
+    let mut text = String::with_capacity(source.len());
+    let mut copied = 0;
+
+    for (start, (end, edits)) in lines {
+        text.push_str(&source[copied..start]);
+        text.push_str(&edit_line(&source[start..end], &edits));
+        copied = end;
+    }
+    text.push_str(&source[copied..]);
+
+    Fixed { text, fixed }
+}
@@ -0,0 +1,183 @@
+use config::Config;
+use lints::{Lint, LintInfo, Lints, Warning, LINT_FAILED};
+use source::SourceMap;
+use suppress::{Suppressions, UNUSED_SUPPRESSION};
+use vts_units::Scenario;
+
+pub mod baseline;
+pub mod campaign;
+pub mod config;
+pub mod fix;
+pub mod lints;
+pub mod report;
+pub mod source;
+pub mod suppress;
+
+/// Lints which aren't in the [lints::registry], because they don't run on a
+/// single scenario.
+pub fn builtin_rules() -> Vec<&'static LintInfo> {
+    let mut rules = vec![&UNUSED_SUPPRESSION, &LINT_FAILED];
+    rules.extend(campaign::RULES);
+    rules
+}
+
+/// Every lint there is, sorted by code.
+pub fn all_rules() -> Vec<&'static LintInfo> {
+    let mut rules = lints::registry().rules();
+    rules.extend(builtin_rules());
+    rules.sort_by_key(|r| r.code);
+    rules
+}
+
+/// Everything needed to lint scenarios covered by one config.
+pub struct Setup {
+    pub config: Config,
+    pub lints: Lints,
+    /// The lints which can show up in the findings.
+    pub enabled: Vec<&'static LintInfo>,
+}
+
+impl Setup {
+    /// `disable` and `enable` override the config, `enable` wins.
+    pub fn new(
+        config: Config,
+        rules: &[&'static LintInfo],
+        disable: &[&'static LintInfo],
+        enable: &[&'static LintInfo],
+    ) -> Setup {
+        let mut disabled: Vec<_> = rules
+            .iter()
+            .copied()
+            .filter(|r| config.is_allowed(r))
+            .collect();
+        disabled.extend(disable);
+        disabled.retain(|d| !enable.contains(d));
+
+        let mut lints = lints::registry();
+        lints.retain(|info| !disabled.contains(&info));
+
+        let mut enabled = lints.rules();
+        enabled.extend(
+            builtin_rules()
+                .into_iter()
+                .filter(|r| !disabled.contains(r)),
+        );
+
+        Setup {
+            config,
+            lints,
+            enabled,
+        }
+    }
+}
+
+/// Findings of one scenario, and whether a lint failed on it. Failures which
+/// are allowed or suppressed don't count, there's nothing left to explain them.
+pub struct ScenarioFindings {
+    pub warnings: Vec<Warning>,
+    pub lint_failed: bool,
+}
+
+/// Lints one scenario, `source` is its text. `extra` are findings from
+/// outside of the scenario, e.g. from the [campaign] checks.
+pub fn lint_scenario(
+    scenario: &Scenario,
+    source: &str,
+    setup: &Setup,
+    rules: &[&'static LintInfo],
+    extra: Vec<Warning>,
+) -> ScenarioFindings {
+    let enabled = &setup.enabled;
+
+    let result = setup.lints.lint(scenario);
+    let mut warnings = result.warnings;
+    if enabled.contains(&&LINT_FAILED) {
+        warnings.extend(result.failures.iter().map(|f| f.to_warning()));
+    }
+    warnings.extend(extra.into_iter().filter(|w| enabled.contains(&w.lint)));
+
+    let mut suppressions = Suppressions::collect(scenario, rules);
+    suppressions.apply(&mut warnings);
+    if enabled.contains(&&UNUSED_SUPPRESSION) {
+        warnings.extend(suppressions.unused(enabled));
+    }
+
+    setup.config.apply(&mut warnings);
+
+    let source = SourceMap::new(source);
+    for w in &mut warnings {
+        w.span = source.locate(w);
+        w.related_span = source.locate_related(w);
+    }
+
+    // Units are kept in a `HashMap`, sort so reports are the same every run.
+    warnings.sort_by_key(|w| (w.span.map(|s| s.start), w.lint.code, w.unit_id()));
+
+    let lint_failed = warnings.iter().any(|w| w.lint == &LINT_FAILED);
+
+    ScenarioFindings {
+        warnings,
+        lint_failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vts_parsing::parse::try_parse;
+
+    use super::*;
+    use crate::config::LintLevel;
+
+    /// UnitIDTesting.vts with the `decoyUnits` of the MW truck with id 16
+    /// removed, so mw-has-radar fails on it.
+    fn broken_scenario(description: &str) -> String {
+        let source = include_str!("../../UnitIDTesting.vts");
+        let truck = source.find("unitInstanceID = 16").unwrap();
+        let decoys = truck + source[truck..].find("decoyUnits = \n").unwrap();
+        let line_start = source[..decoys].rfind('\n').unwrap() + 1;
+        let line_end = decoys + "decoyUnits = \n".len();
+
+        let source = format!("{}{}", &source[..line_start], &source[line_end..]);
+        source.replacen(
+            "scenarioDescription = ",
+            &format!("scenarioDescription = {description}"),
+            1,
+        )
+    }
+
+    fn lint(source: &str, config: Config) -> ScenarioFindings {
+        let node = try_parse(source).unwrap();
+        let scenario = Scenario::from_node_lenient(&node).unwrap();
+        let rules = all_rules();
+        let setup = Setup::new(config, &rules, &[], &[]);
+
+        lint_scenario(&scenario, source, &setup, &rules, vec![])
+    }
+
+    #[test]
+    fn lint_failed() {
+        let findings = lint(&broken_scenario(""), Config::default());
+        assert!(findings.lint_failed);
+    }
+
+    #[test]
+    fn lint_failed_allowed() {
+        let config = Config {
+            lints: vec![(&LINT_FAILED, LintLevel::Allow)],
+            ..Config::default()
+        };
+
+        let findings = lint(&broken_scenario(""), config);
+        assert!(!findings.lint_failed);
+        assert!(findings.warnings.iter().all(|w| w.lint != &LINT_FAILED));
+    }
+
+    #[test]
+    fn lint_failed_suppressed() {
+        let source = broken_scenario("[lint:allow lint-failed]");
+
+        let findings = lint(&source, Config::default());
+        assert!(!findings.lint_failed);
+        assert!(findings.warnings.iter().all(|w| w.lint != &LINT_FAILED));
+    }
+}
@@ -1,41 +1,414 @@
 use std::any::Any;
+use std::collections::HashMap;
 
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
 use vts_units::fields::AccessFieldsError;
-use vts_units::{Scenario, UnitRef};
+use vts_units::{AnyUnit, Faction, Scenario, UnitRef, UnitType, UnknownUnit};
 
+use crate::source::Span;
+
+pub mod duplicate_id;
 pub mod irmd;
 pub mod mw;
 pub mod sam_launcher;
+pub mod unknown_unit;
 
+/// Static description of a lint.
 #[derive(Debug)]
+pub struct LintInfo {
+    /// Stable identifier, e.g. `VTS0001`. Never reused, even if the lint is removed.
+    pub code: &'static str,
+    /// Kebab-case name used to enable or disable the lint.
+    pub name: &'static str,
+    pub summary: &'static str,
+    /// Shown by `--explain`.
+    pub explanation: &'static str,
+    pub default_severity: Severity,
+    pub category: Category,
+}
+
+impl PartialEq for LintInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+/// Placeholder until [UnitLint] fills in the real lint.
+const UNASSIGNED: LintInfo = LintInfo {
+    code: "",
+    name: "",
+    summary: "",
+    explanation: "",
+    default_severity: Severity::Warning,
+    category: Category::Correctness,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// The scenario almost certainly doesn't do what was intended.
+    Correctness,
+    /// Probably a mistake, but there are valid reasons for it.
+    Suspicious,
+    /// Doesn't change how the scenario plays, but should be cleaned up.
+    Style,
+}
+
+impl Category {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Category::Correctness => "correctness",
+            Category::Suspicious => "suspicious",
+            Category::Style => "style",
+        }
+    }
+}
+
+/// How bad a finding is, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Help,
+    Note,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Help => "help",
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+fn serialize_lint<S: Serializer>(
+    lint: &&'static LintInfo,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct LintId {
+        code: &'static str,
+        name: &'static str,
+    }
+
+    LintId {
+        code: lint.code,
+        name: lint.name,
+    }
+    .serialize(serializer)
+}
+
+/// The unit a [Warning] is about.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitInfo {
+    pub id: i64,
+    pub name: String,
+    pub unit_type: Option<UnitType>,
+    pub faction: Option<Faction>,
+    /// Which of the units with this id it is, in order of appearance. Only
+    /// units in [Scenario::duplicates] have one above 0.
+    pub occurrence: usize,
+}
+
+impl<'a> From<&'a UnitRef<'a>> for UnitInfo {
+    fn from(unit: &'a UnitRef<'a>) -> Self {
+        UnitInfo {
+            id: unit.id(),
+            name: unit.name().to_string(),
+            unit_type: unit.unit_type(),
+            faction: Some(unit.faction()),
+            occurrence: 0,
+        }
+    }
+}
+
+impl<'a> From<&'a UnknownUnit<'a>> for UnitInfo {
+    fn from(unit: &'a UnknownUnit<'a>) -> Self {
+        UnitInfo {
+            id: unit.id(),
+            name: unit.name().to_string(),
+            unit_type: None,
+            faction: None,
+            occurrence: 0,
+        }
+    }
+}
+
+impl<'a> From<&'a AnyUnit<'a>> for UnitInfo {
+    fn from(unit: &'a AnyUnit<'a>) -> Self {
+        match unit {
+            AnyUnit::Known(u) => u.into(),
+            AnyUnit::Unknown(u) => u.into(),
+        }
+    }
+}
+
+/// The units of [Scenario::duplicates], told apart by
+/// [UnitInfo::occurrence].
+pub fn duplicate_units<'s, 'a>(scenario: &'s Scenario<'a>) -> Vec<(&'s AnyUnit<'a>, UnitInfo)> {
+    let mut seen = HashMap::new();
+
+    scenario
+        .duplicates()
+        .iter()
+        .map(|unit| {
+            let occurrence = seen.entry(unit.id()).or_insert(0);
+            *occurrence += 1;
+
+            let info = UnitInfo {
+                occurrence: *occurrence,
+                ..unit.into()
+            };
+            (unit, info)
+        })
+        .collect()
+}
+
+impl std::fmt::Display for UnitInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (id:{})", self.name, self.id)
+    }
+}
+
+/// A machine-applicable change to a key of the unit, or the scenario, a
+/// [Warning] is about.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Edit {
+    /// Replaces the value.
+    Set { field: &'static str, value: String },
+    /// Removes an id from a `;` separated list of `unitInstanceID`s.
+    RemoveId { field: &'static str, id: i64 },
+}
+
+/// A suggested fix for a [Warning], applied by `--fix`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Fix {
+    /// What the fix does, e.g. `Remove id:5 from radarUnits`.
+    pub message: String,
+    pub edits: Vec<Edit>,
+}
+
+impl Fix {
+    pub fn new(message: impl Into<String>, edits: Vec<Edit>) -> Fix {
+        Fix {
+            message: message.into(),
+            edits,
+        }
+    }
+
+    pub fn remove_id(field: &'static str, id: i64) -> Fix {
+        Fix::new(
+            format!("Remove id:{id} from {field}"),
+            vec![Edit::RemoveId { field, id }],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
 pub struct Warning {
-    pub unit_name: String,
-    pub unit_id: i64,
+    /// The lint which produced this warning, set by [UnitLint].
+    #[serde(serialize_with = "serialize_lint")]
+    pub lint: &'static LintInfo,
+    /// Set to [LintInfo::default_severity] by [UnitLint].
+    pub severity: Severity,
+    /// `None` if this is about the scenario as a whole.
+    pub unit: Option<UnitInfo>,
+    /// The key of the unit, its `UnitFields` or the scenario this is about,
+    /// e.g. `radarUnits`.
+    pub field: Option<&'static str>,
+    #[serde(rename = "message")]
     pub description: String,
+    /// Filled in from the [SourceMap](crate::source::SourceMap) after linting.
+    pub span: Option<Span>,
+    /// Another unit the warning points to, e.g. the first unit with a
+    /// duplicated id.
+    pub related: Option<UnitInfo>,
+    /// Where [Warning::related] is, filled in like [Warning::span].
+    pub related_span: Option<Span>,
+    pub fix: Option<Fix>,
+}
+
+impl Warning {
+    pub fn new(
+        lint: &'static LintInfo,
+        unit: Option<UnitInfo>,
+        description: impl Into<String>,
+    ) -> Warning {
+        Warning {
+            lint,
+            severity: lint.default_severity,
+            unit,
+            field: None,
+            description: description.into(),
+            span: None,
+            related: None,
+            related_span: None,
+            fix: None,
+        }
+    }
+
+    pub fn with_field(self, field: &'static str) -> Warning {
+        Warning {
+            field: Some(field),
+            ..self
+        }
+    }
+
+    pub fn with_fix(self, fix: Fix) -> Warning {
+        Warning {
+            fix: Some(fix),
+            ..self
+        }
+    }
+
+    pub fn with_related(self, unit: UnitInfo) -> Warning {
+        Warning {
+            related: Some(unit),
+            ..self
+        }
+    }
+
+    pub fn unit_name(&self) -> Option<&str> {
+        self.unit.as_ref().map(|u| u.name.as_str())
+    }
+
+    pub fn unit_id(&self) -> Option<i64> {
+        self.unit.as_ref().map(|u| u.id)
+    }
+}
+
+/// The description, prefixed with the unit if there is one and followed by
+/// the line of the related unit if we know it.
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.unit {
+            Some(ref unit) => write!(f, "{unit}: {}", self.description)?,
+            None => f.write_str(&self.description)?,
+        }
+
+        match (&self.related, self.related_span) {
+            (Some(unit), Some(span)) => write!(f, " ({unit} is at line {})", span.start_line),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl<'a, S: Into<String>> From<(&'a UnitRef<'a>, S)> for Warning {
     fn from((unit, description): (&'a UnitRef<'a>, S)) -> Self {
         Warning {
-            unit_name: unit.name().to_string(),
-            unit_id: unit.id(),
+            lint: &UNASSIGNED,
+            severity: Severity::Warning,
+            unit: Some(unit.into()),
+            field: None,
             description: description.into(),
+            span: None,
+            related: None,
+            related_span: None,
+            fix: None,
         }
     }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum LintError {
-    #[error("Couldn't access fields of {unit} (id:{unit_id})")]
+    #[error("Couldn't access fields of {unit}: {reason}")]
     UnitAccessError {
-        unit: String,
-        unit_id: i64,
+        unit: UnitInfo,
+        #[source]
         reason: AccessFieldsError,
     },
 }
 
+impl LintError {
+    pub fn access(unit: &UnitRef, reason: AccessFieldsError) -> LintError {
+        LintError::UnitAccessError {
+            unit: unit.into(),
+            reason,
+        }
+    }
+
+    /// The field the lint failed on.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            LintError::UnitAccessError { reason, .. } => reason.field(),
+        }
+    }
+
+    /// The unit the lint failed on.
+    pub fn unit(&self) -> Option<&UnitInfo> {
+        match self {
+            LintError::UnitAccessError { unit, .. } => Some(unit),
+        }
+    }
+}
+
+const LINT_FAILED_EXPLANATION: &str = "\
+Lints expect units to have the fields the game writes for them. If a unit is
+missing a field, or a field can't be read, the lint checking that unit can't
+do its job. The other units and lints are still checked.
+
+This usually means the scenario was edited by hand or by another tool. Open
+and save the scenario in the editor, or fix the named field.";
+
+pub const LINT_FAILED: LintInfo = LintInfo {
+    code: "VTS0005",
+    name: "lint-failed",
+    summary: "Lints should be able to check every unit.",
+    explanation: LINT_FAILED_EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
+
+/// A lint which couldn't check a unit.
+#[derive(Debug)]
+pub struct LintFailure {
+    pub lint: &'static LintInfo,
+    pub error: LintError,
+}
+
+impl LintFailure {
+    /// Reports the failure as a [LINT_FAILED] finding.
+    pub fn to_warning(&self) -> Warning {
+        let warning = Warning::new(
+            &LINT_FAILED,
+            self.error.unit().cloned(),
+            format!("{} failed: {}", self.lint.name, self.error),
+        );
+
+        match self.error.field() {
+            Some(field) => warning.with_field(field),
+            None => warning,
+        }
+    }
+}
+
+/// Findings of a lint run, and the units lints failed on.
+#[derive(Debug, Default)]
+pub struct LintResult {
+    pub warnings: Vec<Warning>,
+    pub failures: Vec<LintFailure>,
+}
+
+impl LintResult {
+    pub fn extend(&mut self, other: LintResult) {
+        self.warnings.extend(other.warnings);
+        self.failures.extend(other.failures);
+    }
+}
+
 pub trait Lint {
-    fn lint(&self, scenario: &Scenario) -> Result<Vec<Warning>, LintError>;
+    /// Runs the lint on every unit, failing units don't stop the others
+    /// from being checked.
+    fn lint(&self, scenario: &Scenario) -> LintResult;
+
+    /// Every lint which can show up in [Warning::lint].
+    fn rules(&self) -> Vec<&'static LintInfo>;
 }
 
 pub trait AnyLint: Lint + Any {}
@@ -52,19 +425,48 @@ impl Lints {
         self.lints.push(Box::new(lint));
         self
     }
+
+    /// Keeps only the lints for which `keep` returns true.
+    pub fn retain(&mut self, mut keep: impl FnMut(&'static LintInfo) -> bool) -> &mut Self {
+        self.lints.retain(|l| l.rules().into_iter().any(&mut keep));
+        self
+    }
+}
+
+/// Every lint we have, enabled.
+pub fn registry() -> Lints {
+    let mut lints = Lints::default();
+    lints
+        .add_lint(mw::mw_has_radar())
+        .add_lint(irmd::irmd_linked_to_by_mw())
+        .add_lint(sam_launcher::sam_launcher_attached_to_radar())
+        .add_lint(unknown_unit::UnknownUnitLint)
+        .add_lint(duplicate_id::DuplicateUnitIdLint);
+
+    lints
+}
+
+/// Finds a lint by either its code or its name.
+pub fn find_lint(rules: &[&'static LintInfo], code_or_name: &str) -> Option<&'static LintInfo> {
+    rules
+        .iter()
+        .find(|r| r.code.eq_ignore_ascii_case(code_or_name) || r.name == code_or_name)
+        .copied()
 }
 
 impl Lint for Lints {
-    fn lint(&self, scenario: &Scenario) -> Result<Vec<Warning>, LintError> {
-        self.lints
-            .iter()
-            .map(|l| l.lint(scenario))
-            .try_fold(Vec::new(), |mut acc, res| {
-                res.map(|vec| {
-                    acc.extend(vec);
-                    acc
-                })
-            })
+    fn lint(&self, scenario: &Scenario) -> LintResult {
+        let mut result = LintResult::default();
+
+        for lint in &self.lints {
+            result.extend(lint.lint(scenario));
+        }
+
+        result
+    }
+
+    fn rules(&self) -> Vec<&'static LintInfo> {
+        self.lints.iter().flat_map(|l| l.rules()).collect()
     }
 }
 
@@ -72,6 +474,7 @@ pub struct UnitLint<
     F: Fn(&UnitRef) -> bool,
     G: Fn(&UnitRef, &Scenario) -> Result<Vec<Warning>, LintError>,
 > {
+    info: &'static LintInfo,
     filter: F,
     linter: G,
 }
@@ -79,25 +482,71 @@ pub struct UnitLint<
 impl<F: Fn(&UnitRef) -> bool, G: Fn(&UnitRef, &Scenario) -> Result<Vec<Warning>, LintError>>
     UnitLint<F, G>
 {
-    pub fn new(filter: F, linter: G) -> Self {
-        Self { filter, linter }
+    pub fn new(info: &'static LintInfo, filter: F, linter: G) -> Self {
+        Self {
+            info,
+            filter,
+            linter,
+        }
     }
 }
 
 impl<F: Fn(&UnitRef) -> bool, G: Fn(&UnitRef, &Scenario) -> Result<Vec<Warning>, LintError>> Lint
     for UnitLint<F, G>
 {
-    fn lint(&self, scenario: &Scenario) -> Result<Vec<Warning>, LintError> {
-        scenario
-            .units()
-            .values()
-            .filter(|u| (self.filter)(u))
-            .map(|u| (self.linter)(u, scenario))
-            .try_fold(Vec::new(), |mut acc, res| {
-                res.map(|vec| {
-                    acc.extend(vec);
-                    acc
-                })
-            })
+    fn lint(&self, scenario: &Scenario) -> LintResult {
+        let mut result = LintResult::default();
+
+        let duplicates = duplicate_units(scenario);
+        let duplicates = duplicates.iter().filter_map(|(unit, info)| match unit {
+            AnyUnit::Known(u) => Some((u, info.occurrence)),
+            AnyUnit::Unknown(_) => None,
+        });
+        let units = scenario.units().values().map(|u| (u, 0)).chain(duplicates);
+
+        for (unit, occurrence) in units.filter(|(u, _)| (self.filter)(u)) {
+            // Findings on a duplicate are about that copy, not the first unit
+            // with its id.
+            let mark = |info: &mut UnitInfo| {
+                if info.id == unit.id() {
+                    info.occurrence = occurrence;
+                }
+            };
+
+            match (self.linter)(unit, scenario) {
+                Ok(warnings) => result.warnings.extend(warnings.into_iter().map(|mut w| {
+                    w.unit.as_mut().map(mark);
+                    Warning {
+                        lint: self.info,
+                        severity: self.info.default_severity,
+                        ..w
+                    }
+                })),
+                // Lints looking at other units can fail on the same unit
+                // once for every unit they check.
+                Err(mut error) => {
+                    match &mut error {
+                        LintError::UnitAccessError { unit, .. } => mark(unit),
+                    }
+
+                    let key = error.unit().map(|u| (u.id, u.occurrence));
+                    if !result.failures.iter().any(|f| {
+                        f.lint.code == self.info.code
+                            && f.error.unit().map(|u| (u.id, u.occurrence)) == key
+                    }) {
+                        result.failures.push(LintFailure {
+                            lint: self.info,
+                            error,
+                        });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    fn rules(&self) -> Vec<&'static LintInfo> {
+        vec![self.info]
     }
 }
@@ -0,0 +1,63 @@
+use vts_units::Scenario;
+
+use super::{duplicate_units, Category, Lint, LintInfo, LintResult, Severity, UnitInfo, Warning};
+
+const EXPLANATION: &str = "\
+Units link to each other by their `unitInstanceID`, e.g. in the `radarUnits`
+of a missile warning truck. If two units share an id, only one of them can be
+linked to, and it's not obvious which one the game picks.
+
+This lint fires for every unit whose id was already used by an earlier unit in
+the scenario. Other lints only check links against the earlier unit, so fix
+this first. Usually one of the units was copied by editing the file by hand;
+give it an unused id.";
+
+pub const DUPLICATE_UNIT_ID: LintInfo = LintInfo {
+    code: "VTS0007",
+    name: "duplicate-unit-id",
+    summary: "Every unit should have its own unitInstanceID.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
+
+pub struct DuplicateUnitIdLint;
+
+impl Lint for DuplicateUnitIdLint {
+    fn lint(&self, scenario: &Scenario) -> LintResult {
+        let warnings = duplicate_units(scenario)
+            .into_iter()
+            .map(|(unit, info)| {
+                let id = unit.id();
+                let first: UnitInfo = match scenario.units().get(&id) {
+                    Some(u) => u.into(),
+                    None => scenario
+                        .unknown_units()
+                        .get(&id)
+                        .expect("duplicates have a first unit with their id")
+                        .into(),
+                };
+
+                Warning::new(
+                    &DUPLICATE_UNIT_ID,
+                    Some(info),
+                    format!(
+                        "Unit id {id} of {} is already used by {first}.",
+                        unit.name()
+                    ),
+                )
+                .with_field("unitInstanceID")
+                .with_related(first)
+            })
+            .collect();
+
+        LintResult {
+            warnings,
+            failures: vec![],
+        }
+    }
+
+    fn rules(&self) -> Vec<&'static LintInfo> {
+        vec![&DUPLICATE_UNIT_ID]
+    }
+}
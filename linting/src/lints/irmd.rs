@@ -1,9 +1,25 @@
 use vts_units::{
-    fields::{self, Fields},
+    fields::{self, IdList},
     Scenario, UnitRef, UnitType,
 };
 
-use super::{Lint, LintError, UnitLint};
+use super::{Category, Lint, LintInfo, Severity, UnitLint};
+
+const EXPLANATION: &str = "\
+IRMD launchers are fired by missile warning (MW) trucks, which list them in
+their `defenseUnits`.
+
+This lint fires if no MW truck in the scenario lists the IRMD, in which case
+it will probably never engage incoming missiles.";
+
+pub const IRMD_LINKED_TO_BY_MW: LintInfo = LintInfo {
+    code: "VTS0002",
+    name: "irmd-linked-to-by-mw",
+    summary: "IRMDs should be listed as a defense unit of a missile warning truck.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Warning,
+    category: Category::Suspicious,
+};
 
 pub fn irmd_linked_to_by_mw() -> impl Lint {
     let filter = |unit: &UnitRef| unit.unit_type().is_some_and(|t| t == UnitType::IRMD);
@@ -11,30 +27,26 @@ pub fn irmd_linked_to_by_mw() -> impl Lint {
     let linter = |unit: &UnitRef, scenario: &Scenario| {
         let our_id = unit.id();
 
-        let has_mw = scenario.units().values().try_fold(false, |acc, u| {
-            if !u.unit_type().is_some_and(|t| t == UnitType::MissileWarning) {
-                return Ok(acc);
-            }
-
-            let Fields::MissileWarning {
-                missile_defenses, ..
-            } = fields::access_fields(u).map_err(|e| LintError::UnitAccessError {
-                unit: unit.name().to_string(),
-                unit_id: unit.id(),
-                reason: e,
-            })?
-            else {
-                panic!("this should be guaranteed to be an MW");
-            };
-
-            if missile_defenses.contains(&our_id) {
-                return Ok(true);
-            }
-
-            Ok(acc)
-        })?;
-
-        if !has_mw {
+        // Only `defenseUnits` matters here. MW trucks where it can't be read
+        // are reported by the MW lints, and skipped so the other IRMDs are
+        // still checked.
+        let field = IdList::MissileDefenses.field();
+        let has_mw = scenario.units().values().any(|u| {
+            u.unit_type().is_some_and(|t| t == UnitType::MissileWarning)
+                && u.get_field(field)
+                    .and_then(|v| fields::parse_instance_id_list(v, field).ok())
+                    .is_some_and(|defenses| defenses.contains(&our_id))
+        });
+
+        // We can't know what an unknown unit is, but if it has defense
+        // units, it's most likely a new kind of MW.
+        let has_unknown_mw = scenario.unknown_units().values().any(|u| {
+            u.get_field("defenseUnits")
+                .and_then(|v| v.as_string())
+                .is_some_and(|ids| ids.split(';').any(|id| id.parse() == Ok(our_id)))
+        });
+
+        if !has_mw && !has_unknown_mw {
             return Ok(vec![
                 (unit, "IRMD has no MW linking to it!".to_string()).into()
             ]);
@@ -43,5 +55,5 @@ pub fn irmd_linked_to_by_mw() -> impl Lint {
         Ok(vec![])
     };
 
-    UnitLint::new(filter, linter)
+    UnitLint::new(&IRMD_LINKED_TO_BY_MW, filter, linter)
 }
@@ -1,6 +1,26 @@
 use vts_units::{fields::Fields, Scenario, UnitRef, UnitType};
 
-use super::{Lint, LintError, UnitLint};
+use super::{Category, Fix, Lint, LintError, LintInfo, Severity, UnitLint, Warning};
+
+const EXPLANATION: &str = "\
+Missile warning (MW) trucks use their linked radars to detect incoming
+missiles, so they can order their defense units (e.g. IRMDs) to engage them.
+
+This lint fires if an MW truck has no radars in its `radarUnits`, if a
+linked radar doesn't exist in the scenario, or if a linked unit isn't a
+SAM radar. Without working radars, the MW truck can't do anything.
+`--fix` removes the ids of radars which don't exist.
+
+Decoy MW trucks without radars are a valid reason to allow this lint.";
+
+pub const MW_HAS_RADAR: LintInfo = LintInfo {
+    code: "VTS0001",
+    name: "mw-has-radar",
+    summary: "Missile warning trucks should be linked to existing SAM radars.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Warning,
+    category: Category::Suspicious,
+};
 
 pub fn mw_has_radar() -> impl Lint {
     let filter = |unit: &UnitRef| {
@@ -10,38 +30,43 @@ pub fn mw_has_radar() -> impl Lint {
 
     let linter = |unit: &UnitRef, scenario: &Scenario| {
         let fields =
-            vts_units::fields::access_fields(unit).map_err(|e| LintError::UnitAccessError {
-                unit: unit.name().to_string(),
-                unit_id: unit.id(),
-                reason: e,
-            })?;
+            vts_units::fields::access_fields(unit).map_err(|e| LintError::access(unit, e))?;
 
         let Fields::MissileWarning { radars, .. } = fields else {
             panic!("unit wasn't a missile warning truck!");
         };
 
         if radars.is_empty() {
-            return Ok(vec![(unit, "MW has no Radars!".to_string()).into()]);
+            return Ok(vec![
+                Warning::from((unit, "MW has no Radars!".to_string())).with_field("radarUnits")
+            ]);
         }
 
         Ok(radars
             .iter()
             .filter_map(|u| {
                 let Some(radar) = scenario.units().get(u) else {
-                    return Some((unit, format!("MW radar {} does not exist!", u)).into());
+                    if scenario.unknown_units().contains_key(u) {
+                        return None;
+                    }
+                    return Some(
+                        Warning::from((unit, format!("MW radar {} does not exist!", u)))
+                            .with_field("radarUnits")
+                            .with_fix(Fix::remove_id("radarUnits", *u)),
+                    );
                 };
 
                 if !radar.unit_type().is_some_and(|t| t == UnitType::SAMRadar) {
                     return Some(
-                        (
+                        Warning::from((
                             unit,
                             format!(
                                 "MW radar {} (id:{}) is not a radar.",
                                 radar.name(),
                                 radar.id()
                             ),
-                        )
-                            .into(),
+                        ))
+                        .with_field("radarUnits"),
                     );
                 }
 
@@ -50,5 +75,5 @@ pub fn mw_has_radar() -> impl Lint {
             .collect())
     };
 
-    UnitLint::new(filter, linter)
+    UnitLint::new(&MW_HAS_RADAR, filter, linter)
 }
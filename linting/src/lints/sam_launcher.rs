@@ -5,46 +5,70 @@ use vts_units::{
 
 use crate::lints::LintError;
 
-use super::{Lint, UnitLint};
+use super::{Category, Fix, Lint, LintInfo, Severity, UnitLint, Warning};
+
+const EXPLANATION: &str = "\
+SAM launchers need a SAM radar, listed in their `radarUnits`, to guide
+their missiles.
+
+This lint fires if a SAM launcher has no radars, if a linked radar doesn't
+exist in the scenario, or if a linked unit isn't a SAM radar. Such a
+launcher will never fire. `--fix` removes the ids of radars which don't
+exist.";
+
+pub const SAM_LAUNCHER_ATTACHED_TO_RADAR: LintInfo = LintInfo {
+    code: "VTS0003",
+    name: "sam-launcher-attached-to-radar",
+    summary: "SAM launchers should be linked to existing SAM radars.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Error,
+    category: Category::Correctness,
+};
 
 pub fn sam_launcher_attached_to_radar() -> impl Lint {
     let filter = |unit: &UnitRef| unit.unit_type().is_some_and(|t| t == UnitType::SAMLauncher);
 
     let linter = |unit: &UnitRef, scenario: &Scenario| {
         let Fields::SAMLauncher { radars, .. } =
-            fields::access_fields(unit).map_err(|e| LintError::UnitAccessError {
-                unit: unit.name().to_string(),
-                unit_id: unit.id(),
-                reason: e,
-            })?
+            fields::access_fields(unit).map_err(|e| LintError::access(unit, e))?
         else {
             panic!("unit wasn't a sam launcher");
         };
 
         if radars.is_empty() {
-            return Ok(vec![(unit, "SAM Launcher has no radars!").into()]);
+            return Ok(vec![
+                Warning::from((unit, "SAM Launcher has no radars!")).with_field("radarUnits")
+            ]);
         }
 
         Ok(radars
             .into_iter()
             .filter_map(|u| {
                 let Some(radar) = scenario.units().get(&u) else {
+                    if scenario.unknown_units().contains_key(&u) {
+                        return None;
+                    }
                     return Some(
-                        (unit, format!("SAM Launcher's radar id:{u} does not exist!")).into(),
+                        Warning::from((
+                            unit,
+                            format!("SAM Launcher's radar id:{u} does not exist!"),
+                        ))
+                        .with_field("radarUnits")
+                        .with_fix(Fix::remove_id("radarUnits", u)),
                     );
                 };
 
                 if !radar.unit_type().is_some_and(|t| t == UnitType::SAMRadar) {
                     return Some(
-                        (
+                        Warning::from((
                             unit,
                             format!(
                                 "SAM Launcher's radar {} (id:{}) is not a radar!",
                                 radar.name(),
                                 radar.id()
                             ),
-                        )
-                            .into(),
+                        ))
+                        .with_field("radarUnits"),
                     );
                 }
                 None
@@ -52,5 +76,5 @@ pub fn sam_launcher_attached_to_radar() -> impl Lint {
             .collect())
     };
 
-    UnitLint::new(filter, linter)
+    UnitLint::new(&SAM_LAUNCHER_ATTACHED_TO_RADAR, filter, linter)
 }
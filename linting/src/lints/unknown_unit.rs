@@ -0,0 +1,58 @@
+use vts_units::{AnyUnit, Scenario};
+
+use super::{duplicate_units, Category, Lint, LintInfo, LintResult, Severity, UnitInfo, Warning};
+
+const EXPLANATION: &str = "\
+The linter only knows the units of the game version it was built for. Units
+added in newer versions of the game, or by mods, are loaded without their
+type or faction, so no lint can check them. Links to them from other units
+aren't reported as missing either.
+
+This lint fires for every unit with an unknown `unitID`. Update the linter if
+the unit is from a newer version of the game.";
+
+pub const UNKNOWN_UNIT: LintInfo = LintInfo {
+    code: "VTS0006",
+    name: "unknown-unit",
+    summary: "Units should be known to the linter, so they can be checked.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Warning,
+    category: Category::Suspicious,
+};
+
+pub struct UnknownUnitLint;
+
+impl Lint for UnknownUnitLint {
+    fn lint(&self, scenario: &Scenario) -> LintResult {
+        let duplicates = duplicate_units(scenario);
+        let duplicates = duplicates.iter().filter_map(|(unit, info)| match unit {
+            AnyUnit::Unknown(u) => Some((u, info.clone())),
+            AnyUnit::Known(_) => None,
+        });
+        let units = scenario
+            .unknown_units()
+            .values()
+            .map(|u| (u, UnitInfo::from(u)))
+            .chain(duplicates);
+
+        let warnings = units
+            .map(|(unit, info)| {
+                Warning::new(
+                    &UNKNOWN_UNIT,
+                    Some(info),
+                    format!("Unknown unit {}, it won't be checked.", unit.unit_id()),
+                )
+                .with_field("unitID")
+            })
+            .collect();
+
+        LintResult {
+            warnings,
+            failures: vec![],
+        }
+    }
+
+    fn rules(&self) -> Vec<&'static LintInfo> {
+        vec![&UNKNOWN_UNIT]
+    }
+}
@@ -1,25 +1,874 @@
-use lints::{Lint, Lints};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use compare::{Old, Resolved};
+use similar::TextDiff;
+use vts_linting::baseline::{self, Baseline};
+use vts_linting::campaign::{self, Member};
+use vts_linting::config::{Config, ConfigError};
+use vts_linting::lints::{find_lint, LintInfo, Severity, Warning};
+use vts_linting::report::{Format, ScenarioReport};
+use vts_linting::{all_rules, lint_scenario, Setup};
 use vts_parsing::parse::try_parse;
+use vts_parsing::Node;
+use vts_units::campaign::Campaign;
 use vts_units::Scenario;
+use watch::{Findings, Snapshot};
+
+mod compare;
+mod watch;
+
+/// Exit codes of the binary, see `--help`.
+#[repr(u8)]
+enum Exit {
+    Clean = 0,
+    Findings = 1,
+    /// Also used by clap for invalid arguments.
+    Usage = 2,
+    InvalidScenario = 3,
+    InternalError = 4,
+}
+
+impl From<Exit> for ExitCode {
+    fn from(exit: Exit) -> ExitCode {
+        ExitCode::from(exit as u8)
+    }
+}
+
+const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0  no findings at or above the threshold
+  1  findings at or above the threshold (see --deny and --max-warnings),
+     or with --fix-dry-run, fixes which would be applied
+  2  invalid arguments or configuration
+  3  a scenario couldn't be read, parsed, converted or written
+  4  a lint failed to check a unit (reported as lint-failed), or the output
+     couldn't be written";
+
+#[derive(Parser)]
+#[command(version, about = "Lints VTOL VR scenarios", after_help = EXIT_CODES_HELP)]
+struct Args {
+    /// Scenarios (.vts) and campaigns (.vtc) to lint, directories are searched
+    /// for them recursively.
+    #[arg(required_unless_present_any = ["list_lints", "explain"])]
+    scenarios: Vec<PathBuf>,
+    /// How findings are printed [default: human, or the format from vts-lint.toml,
+    /// which has to be the same in every config used].
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+    /// Use this config instead of looking for a vts-lint.toml next to each scenario.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Fail if there are findings of this severity or worse.
+    #[arg(long, value_enum, default_value_t = Severity::Error)]
+    deny: Severity,
+    /// Fail if there are more than this many findings.
+    #[arg(long)]
+    max_warnings: Option<usize>,
+    /// Don't run this lint, by name or code. Can be given multiple times.
+    #[arg(long, value_name = "LINT")]
+    disable: Vec<String>,
+    /// Run this lint, even if it's disabled otherwise. Can be given multiple times.
+    #[arg(long, value_name = "LINT")]
+    enable: Vec<String>,
+    /// Lint again whenever a scenario, campaign or config changes.
+    #[arg(long)]
+    watch: bool,
+    /// Apply the suggested fixes and print what changed as a unified diff,
+    /// then report the remaining findings.
+    #[arg(long, conflicts_with_all = ["watch", "fix_dry_run"])]
+    fix: bool,
+    /// Print the unified diff --fix would apply, without changing any files.
+    #[arg(long, conflicts_with = "watch")]
+    fix_dry_run: bool,
+    /// Only report findings which aren't in this baseline, see --write-baseline.
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+    /// Write all current findings to the --baseline file, instead of reporting them.
+    #[arg(
+        long,
+        requires = "baseline",
+        conflicts_with_all = ["watch", "fix", "fix_dry_run"]
+    )]
+    write_baseline: bool,
+    /// Only report findings introduced since OLD, a version of the scenario
+    /// or a git revision, and list those resolved since.
+    #[arg(
+        long,
+        value_name = "OLD",
+        conflicts_with_all = ["watch", "fix", "fix_dry_run", "write_baseline"]
+    )]
+    diff: Option<String>,
+    /// List all lints and exit.
+    #[arg(long)]
+    list_lints: bool,
+    /// Explain a lint, by name or code, and exit.
+    #[arg(long, value_name = "LINT")]
+    explain: Option<String>,
+}
+
+fn list_lints(out: &mut dyn Write, rules: &[&'static LintInfo]) -> io::Result<()> {
+    for r in rules {
+        writeln!(
+            out,
+            "{}  {:<32} {:<8} {:<12} {}",
+            r.code,
+            r.name,
+            r.default_severity.as_str(),
+            r.category.as_str(),
+            r.summary
+        )?;
+    }
+
+    Ok(())
+}
+
+fn explain(out: &mut dyn Write, lint: &LintInfo) -> io::Result<()> {
+    writeln!(out, "{} {}", lint.code, lint.name)?;
+    writeln!(
+        out,
+        "default severity: {}, category: {}",
+        lint.default_severity.as_str(),
+        lint.category.as_str()
+    )?;
+    writeln!(out)?;
+    writeln!(out, "{}", lint.summary)?;
+    writeln!(out)?;
+    writeln!(out, "{}", lint.explanation)
+}
+
+/// `exit`, unless writing the output failed. A closed pipe, e.g. from
+/// `| head`, isn't an error, the reader just didn't want the rest.
+fn exit_after(written: io::Result<()>, exit: Exit) -> Exit {
+    match written {
+        Ok(()) => exit,
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => exit,
+        Err(e) => {
+            eprintln!("Couldn't write output: {e}");
+            Exit::InternalError
+        }
+    }
+}
+
+/// `--disable` and `--enable`, resolved to lints.
+struct LintArgs {
+    disable: Vec<&'static LintInfo>,
+    enable: Vec<&'static LintInfo>,
+}
+
+fn resolve_lints(
+    rules: &[&'static LintInfo],
+    names: &[String],
+) -> Result<Vec<&'static LintInfo>, String> {
+    names
+        .iter()
+        .map(|name| {
+            find_lint(rules, name).ok_or_else(|| format!("Unknown lint {name}, see --list-lints"))
+        })
+        .collect()
+}
+
+/// Something to lint, found on the command line.
+enum Input {
+    Scenario(PathBuf),
+    /// The `.vtc`, its scenarios are in the directories next to it.
+    Campaign(PathBuf),
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// The entries of a directory, in a stable order.
+fn read_dir_sorted(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut entries = std::fs::read_dir(dir)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    Ok(entries)
+}
+
+/// Adds `path` if it's a file. If it's a directory, adds the campaign in it,
+/// or searches it for scenarios and campaigns.
+fn find_inputs(path: &Path, found: &mut Vec<Input>) -> io::Result<()> {
+    if !path.is_dir() {
+        found.push(match has_extension(path, "vtc") {
+            true => Input::Campaign(path.to_path_buf()),
+            false => Input::Scenario(path.to_path_buf()),
+        });
+        return Ok(());
+    }
+
+    let entries = read_dir_sorted(path)?;
+
+    if let Some(campaign) = entries
+        .iter()
+        .find(|e| e.is_file() && has_extension(e, "vtc"))
+    {
+        found.push(Input::Campaign(campaign.clone()));
+        return Ok(());
+    }
+
+    for entry in entries {
+        if entry.is_dir() {
+            find_inputs(&entry, found)?;
+        } else if has_extension(&entry, "vts") {
+            found.push(Input::Scenario(entry));
+        }
+    }
+
+    Ok(())
+}
+
+/// All `.vts` files below `dir`.
+fn find_scenarios(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in read_dir_sorted(dir)? {
+        if entry.is_dir() {
+            find_scenarios(&entry, found)?;
+        } else if has_extension(&entry, "vts") {
+            found.push(entry);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads and parses a scenario or campaign, the error is printable.
+fn load(path: &Path) -> Result<(String, Node), String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+
+    let parsed =
+        try_parse(&data).map_err(|e| format!("Couldn't parse {}:\n{e}", path.display()))?;
+
+    Ok((data, parsed))
+}
+
+fn to_scenario<'a>(path: &Path, node: &'a Node) -> Result<Scenario<'a>, String> {
+    Scenario::from_node_lenient(node)
+        .map_err(|e| format!("Invalid scenario {}: {e}", path.display()))
+}
+
+/// Findings of one scenario, and whether any lint failed on it.
+struct Linted {
+    path: PathBuf,
+    data: String,
+    warnings: Vec<Warning>,
+    lint_failed: bool,
+}
+
+/// Lints one scenario, see [vts_linting::lint_scenario].
+fn lint(
+    path: PathBuf,
+    data: String,
+    scenario: &Scenario,
+    setup: &Setup,
+    rules: &[&'static LintInfo],
+    extra: Vec<Warning>,
+) -> Linted {
+    let findings = lint_scenario(scenario, &data, setup, rules, extra);
+
+    Linted {
+        path,
+        data,
+        warnings: findings.warnings,
+        lint_failed: findings.lint_failed,
+    }
+}
+
+/// Loads the config for `path` the first time it's needed.
+struct Setups<'a> {
+    /// Keyed by the config file, scenarios next to each other usually share one.
+    setups: HashMap<Option<PathBuf>, Setup>,
+    config: Option<&'a Path>,
+    rules: &'a [&'static LintInfo],
+    lint_args: &'a LintArgs,
+}
+
+impl Setups<'_> {
+    /// The config used for `path`, `None` for the default one.
+    fn config_path(&self, path: &Path) -> Option<PathBuf> {
+        self.config
+            .map(Path::to_path_buf)
+            .or_else(|| Config::discover(path))
+    }
+
+    fn get(&mut self, path: &Path) -> Result<&Setup, ConfigError> {
+        Ok(match self.setups.entry(self.config_path(path)) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let config = match entry.key() {
+                    Some(config) => Config::load(config, self.rules)?,
+                    None => Config::default(),
+                };
+
+                entry.insert(Setup::new(
+                    config,
+                    self.rules,
+                    &self.lint_args.disable,
+                    &self.lint_args.enable,
+                ))
+            }
+        })
+    }
+}
+
+/// The state of linting everything given on the command line.
+struct Run<'a> {
+    setups: Setups<'a>,
+    /// `--format`, which wins over the configs.
+    format: Option<Format>,
+    /// The first `format` set by a config, and that config.
+    config_format: Option<(Format, PathBuf)>,
+    linted: Vec<Linted>,
+    /// Scenarios which couldn't be read, parsed or converted.
+    load_failed: usize,
+    campaigns: usize,
+    /// Campaigns which couldn't be read or whose directory couldn't be.
+    campaigns_failed: usize,
+}
+
+impl Run<'_> {
+    /// Remembers the `format` of the config for `path`. Configs setting
+    /// different formats are an error, unless `--format` chooses one.
+    fn config_format(&mut self, path: &Path) -> Result<(), ConfigError> {
+        let Some(format) = self.setups.get(path)?.config.format else {
+            return Ok(());
+        };
+        let config = self.setups.config_path(path).unwrap_or_default();
+
+        match self.config_format {
+            None => self.config_format = Some((format, config)),
+            Some((first, ref first_config)) if first != format && self.format.is_none() => {
+                return Err(ConfigError::ConflictingFormat {
+                    first: first_config.clone(),
+                    second: config,
+                });
+            }
+            Some(_) => {}
+        }
+
+        Ok(())
+    }
+
+    fn scenario(&mut self, path: PathBuf) -> Result<(), ConfigError> {
+        self.config_format(&path)?;
+        let rules = self.setups.rules;
+        let setup = self.setups.get(&path)?;
+
+        let loaded = load(&path).and_then(|(data, node)| {
+            let scenario = to_scenario(&path, &node)?;
+            Ok(lint(path, data, &scenario, setup, rules, vec![]))
+        });
+
+        match loaded {
+            Ok(linted) => self.linted.push(linted),
+            Err(e) => {
+                eprintln!("{e}");
+                self.load_failed += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn campaign(&mut self, path: PathBuf) -> Result<(), ConfigError> {
+        self.campaigns += 1;
+
+        let (_, node) = match load(&path) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("{e}");
+                self.campaigns_failed += 1;
+                return Ok(());
+            }
+        };
+
+        let campaign = match Campaign::try_from(&node) {
+            Ok(campaign) => campaign,
+            Err(e) => {
+                eprintln!("Invalid campaign {}: {e}", path.display());
+                self.campaigns_failed += 1;
+                return Ok(());
+            }
+        };
+
+        let dir = compare::directory(&path);
+        let mut paths = Vec::new();
+        if let Err(e) = find_scenarios(dir, &mut paths) {
+            eprintln!("Couldn't read {}: {e}", dir.display());
+            self.campaigns_failed += 1;
+            return Ok(());
+        }
+
+        let mut loaded = Vec::new();
+        for path in paths {
+            match load(&path) {
+                Ok((data, node)) => loaded.push((path, data, node)),
+                Err(e) => {
+                    eprintln!("{e}");
+                    self.load_failed += 1;
+                }
+            }
+        }
+
+        let mut scenarios = Vec::new();
+        for (path, data, node) in &loaded {
+            match to_scenario(path, node) {
+                Ok(scenario) => scenarios.push((path, data, scenario)),
+                Err(e) => {
+                    eprintln!("{e}");
+                    self.load_failed += 1;
+                }
+            }
+        }
+
+        let members: Vec<_> = scenarios
+            .iter()
+            .map(|(path, _, scenario)| Member { path, scenario })
+            .collect();
+        let campaign_warnings = campaign::check(&campaign, &members);
+
+        let rules = self.setups.rules;
+        for ((path, data, scenario), extra) in scenarios.iter().zip(campaign_warnings) {
+            self.config_format(path)?;
+            let setup = self.setups.get(path)?;
+
+            self.linted.push(lint(
+                path.to_path_buf(),
+                data.to_string(),
+                scenario,
+                setup,
+                rules,
+                extra,
+            ));
+        }
+
+        Ok(())
+    }
+}
 
-pub mod lints;
+/// How the baseline changed the findings.
+struct BaselineSummary {
+    hidden: usize,
+    fixed: usize,
+}
+
+/// Everything linted in one go.
+struct Outcome {
+    linted: Vec<Linted>,
+    /// Enabled by the config of any scenario.
+    enabled: Vec<&'static LintInfo>,
+    format: Format,
+    /// Scenarios which couldn't be read, parsed or converted.
+    load_failed: usize,
+    campaigns: usize,
+    campaigns_failed: usize,
+    /// `None` unless filtered by a baseline.
+    baseline: Option<BaselineSummary>,
+}
+
+/// Lints everything given on the command line, the error is printable and
+/// means the arguments or a config are invalid.
+fn lint_all(
+    args: &Args,
+    rules: &[&'static LintInfo],
+    lint_args: &LintArgs,
+) -> Result<Outcome, String> {
+    let mut load_failed = 0;
+
+    let mut inputs = Vec::new();
+    for path in &args.scenarios {
+        if let Err(e) = find_inputs(path, &mut inputs) {
+            eprintln!("Couldn't read {}: {e}", path.display());
+            load_failed += 1;
+        }
+    }
+
+    if inputs.is_empty() && load_failed == 0 {
+        return Err("No scenarios (.vts) or campaigns (.vtc) found".to_string());
+    }
+
+    let mut run = Run {
+        setups: Setups {
+            setups: HashMap::new(),
+            config: args.config.as_deref(),
+            rules,
+            lint_args,
+        },
+        format: args.format,
+        config_format: None,
+        linted: Vec::new(),
+        load_failed,
+        campaigns: 0,
+        campaigns_failed: 0,
+    };
+
+    for input in inputs {
+        match input {
+            Input::Scenario(path) => run.scenario(path),
+            Input::Campaign(path) => run.campaign(path),
+        }
+        .map_err(|e| e.to_string())?;
+    }
+
+    let mut enabled: Vec<&'static LintInfo> = Vec::new();
+    for rule in run.setups.setups.values().flat_map(|s| &s.enabled) {
+        if !enabled.contains(rule) {
+            enabled.push(rule);
+        }
+    }
+    enabled.sort_by_key(|r| r.code);
+
+    let mut linted = run.linted;
+
+    let baseline = match args.baseline {
+        Some(ref path) if !args.write_baseline => {
+            let mut baseline = Baseline::load(path).map_err(|e| e.to_string())?;
+            for l in &mut linted {
+                baseline.filter(&l.path, &mut l.warnings);
+            }
+
+            Some(BaselineSummary {
+                hidden: baseline.hidden(),
+                fixed: baseline.fixed(),
+            })
+        }
+        _ => None,
+    };
+
+    Ok(Outcome {
+        linted,
+        enabled,
+        format: run
+            .format
+            .or(run.config_format.map(|(format, _)| format))
+            .unwrap_or(Format::Human),
+        load_failed: run.load_failed,
+        campaigns: run.campaigns,
+        campaigns_failed: run.campaigns_failed,
+        baseline,
+    })
+}
+
+fn print_report(outcome: &Outcome) -> io::Result<()> {
+    let reports: Vec<_> = outcome
+        .linted
+        .iter()
+        .map(|l| ScenarioReport {
+            path: &l.path,
+            source: &l.data,
+            warnings: &l.warnings,
+        })
+        .collect();
+
+    outcome
+        .format
+        .reporter()
+        .report(&mut anstream::stdout().lock(), &outcome.enabled, &reports)?;
+
+    if let Some(ref baseline) = outcome.baseline {
+        eprintln!(
+            "{} known findings hidden by the baseline, {} fixed since it was written",
+            baseline.hidden, baseline.fixed
+        );
+    }
+
+    if outcome.load_failed > 0 {
+        eprintln!(
+            "Couldn't lint {} of {} scenarios",
+            outcome.load_failed,
+            outcome.load_failed + outcome.linted.len()
+        );
+    }
+
+    if outcome.campaigns_failed > 0 {
+        eprintln!(
+            "Couldn't lint {} of {} campaigns",
+            outcome.campaigns_failed, outcome.campaigns
+        );
+    }
+
+    Ok(())
+}
+
+/// Applies the suggested fixes, printing a unified diff of every scenario
+/// they change. Files are only written if not `dry_run`.
+///
+/// Returns how many scenarios changed, and whether any couldn't be written.
+fn fix(linted: &[Linted], dry_run: bool) -> io::Result<(usize, bool)> {
+    let mut out = anstream::stdout().lock();
+    let mut fixed = 0;
+    let mut changed = 0;
+    let mut write_failed = false;
+
+    for l in linted {
+        let result = vts_linting::fix::apply(&l.data, &l.warnings);
+        if result.text == l.data {
+            continue;
+        }
+
+        let path = l.path.display().to_string();
+        let diff = TextDiff::from_lines(&l.data, &result.text);
+        write!(out, "{}", diff.unified_diff().header(&path, &path))?;
+
+        if !dry_run {
+            if let Err(e) = std::fs::write(&l.path, &result.text) {
+                eprintln!("Couldn't write {path}: {e}");
+                write_failed = true;
+                continue;
+            }
+        }
+
+        fixed += result.fixed;
+        changed += 1;
+    }
+
+    let verb = if dry_run { "Would fix" } else { "Fixed" };
+    let plural = |n| if n == 1 { "" } else { "s" };
+    eprintln!(
+        "{verb} {fixed} finding{} in {changed} scenario{}",
+        plural(fixed),
+        plural(changed)
+    );
+
+    Ok((changed, write_failed))
+}
+
+/// Lints the old version of a scenario for `--diff`, the error is printable.
+fn lint_old(path: &Path, data: &str, setups: &mut Setups) -> Result<Vec<Warning>, String> {
+    let node = try_parse(data).map_err(|e| format!("Couldn't parse {}:\n{e}", path.display()))?;
+    let scenario = to_scenario(path, &node)?;
+
+    let rules = setups.rules;
+    let setup = setups.get(path).map_err(|e| e.to_string())?;
+
+    Ok(lint_scenario(&scenario, data, setup, rules, vec![]).warnings)
+}
+
+/// Replaces the findings of `outcome` with those introduced since `old`,
+/// returns `old` and the findings resolved since. The error is printable.
+///
+/// Campaign checks need every scenario of the campaign, so they're left out.
+fn diff(
+    args: &Args,
+    rules: &[&'static LintInfo],
+    lint_args: &LintArgs,
+    old: &str,
+    outcome: &mut Outcome,
+) -> Result<(Old, Resolved), String> {
+    let old = Old::new(old, &args.scenarios)?;
+
+    if matches!(old, Old::File(_)) && outcome.linted.len() != 1 {
+        return Err("--diff with a file needs exactly one scenario to compare it to".to_string());
+    }
+
+    let mut setups = Setups {
+        setups: HashMap::new(),
+        config: args.config.as_deref(),
+        rules,
+        lint_args,
+    };
+
+    let mut resolved = Vec::new();
+
+    for l in &mut outcome.linted {
+        let old_warnings = match old.load(&l.path)? {
+            Some(data) => lint_old(&l.path, &data, &mut setups).unwrap_or_else(|e| {
+                eprintln!("{e}\nComparing against no findings");
+                vec![]
+            }),
+            None => vec![],
+        };
+
+        let mut new_warnings = std::mem::take(&mut l.warnings);
+        new_warnings.retain(|w| !campaign::RULES.contains(&w.lint));
+
+        let (introduced, gone) = compare::changes(old_warnings, new_warnings);
+        l.warnings = introduced;
+
+        if !gone.is_empty() {
+            resolved.push((l.path.clone(), gone));
+        }
+    }
+
+    Ok((old, resolved))
+}
+
+/// Lints again whenever something changes, until the user stops us or the
+/// output can't be written anymore.
+fn watch(args: &Args, rules: &[&'static LintInfo], lint_args: &LintArgs) -> Exit {
+    let mut previous = None;
 
-fn main() {
-    let file = std::env::args_os().nth(1).expect("1 argument expected");
+    loop {
+        let snapshot = Snapshot::take(&args.scenarios, args.config.as_deref());
 
-    let data = std::fs::read_to_string(file).unwrap();
+        watch::clear_screen();
+        if let Err(e) = watch_round(args, rules, lint_args, &mut previous) {
+            return exit_after(Err(e), Exit::Clean);
+        }
 
-    let parsed = try_parse(&data).unwrap();
+        snapshot.wait_for_change();
+    }
+}
+
+/// Lints once for `--watch`, `previous` are the findings of the last round.
+fn watch_round(
+    args: &Args,
+    rules: &[&'static LintInfo],
+    lint_args: &LintArgs,
+    previous: &mut Option<Findings>,
+) -> io::Result<()> {
+    match lint_all(args, rules, lint_args) {
+        Ok(outcome) => {
+            print_report(&outcome)?;
+
+            let findings = Findings::new(&outcome.linted);
+            if let Some(ref previous) = previous {
+                watch::print_changes(&mut anstream::stdout().lock(), previous, &findings)?;
+            }
+            *previous = Some(findings);
+        }
+        Err(e) => eprintln!("{e}"),
+    }
+
+    writeln!(
+        anstream::stdout(),
+        "Watching for changes, press Ctrl-C to stop."
+    )
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let rules = all_rules();
+
+    if args.list_lints {
+        return exit_after(list_lints(&mut io::stdout().lock(), &rules), Exit::Clean).into();
+    }
 
-    let scenario: Scenario = (&parsed).try_into().unwrap();
+    if let Some(ref lint) = args.explain {
+        let Some(lint) = find_lint(&rules, lint) else {
+            eprintln!("Unknown lint {lint}, see --list-lints");
+            return Exit::Usage.into();
+        };
+
+        return exit_after(explain(&mut io::stdout().lock(), lint), Exit::Clean).into();
+    }
+
+    let lint_args = match (
+        resolve_lints(&rules, &args.disable),
+        resolve_lints(&rules, &args.enable),
+    ) {
+        (Ok(disable), Ok(enable)) => LintArgs { disable, enable },
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            return Exit::Usage.into();
+        }
+    };
+
+    if args.watch {
+        return watch(&args, &rules, &lint_args).into();
+    }
+
+    let mut outcome = match lint_all(&args, &rules, &lint_args) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            eprintln!("{e}");
+            return Exit::Usage.into();
+        }
+    };
+
+    if let (true, Some(path)) = (args.write_baseline, &args.baseline) {
+        let findings: Vec<_> = outcome
+            .linted
+            .iter()
+            .map(|l| (l.path.as_path(), l.warnings.as_slice()))
+            .collect();
+
+        return match baseline::write(path, &findings) {
+            Ok(count) => {
+                eprintln!("Wrote {count} findings to {}", path.display());
+
+                // The findings are known now, only failures are left.
+                match exit(&args, &outcome) {
+                    Exit::Findings => Exit::Clean,
+                    exit => exit,
+                }
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                Exit::Usage
+            }
+        }
+        .into();
+    }
+
+    if let Some(ref old) = args.diff {
+        let (old, resolved) = match diff(&args, &rules, &lint_args, old, &mut outcome) {
+            Ok(diffed) => diffed,
+            Err(e) => {
+                eprintln!("{e}");
+                return Exit::Usage.into();
+            }
+        };
+
+        let written = print_report(&outcome).and_then(|()| {
+            compare::print_resolved(&mut anstream::stderr().lock(), &old, &resolved)
+        });
+
+        return exit_after(written, exit(&args, &outcome)).into();
+    }
+
+    if args.fix || args.fix_dry_run {
+        let (changed, write_failed) = match fix(&outcome.linted, args.fix_dry_run) {
+            Ok(fixed) => fixed,
+            Err(e) => return exit_after(Err(e), exit(&args, &outcome)).into(),
+        };
+
+        if write_failed {
+            return Exit::InvalidScenario.into();
+        }
+
+        if args.fix_dry_run {
+            return match changed {
+                0 => exit(&args, &outcome),
+                _ => Exit::Findings,
+            }
+            .into();
+        }
+
+        outcome = match lint_all(&args, &rules, &lint_args) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                eprintln!("{e}");
+                return Exit::Usage.into();
+            }
+        };
+    }
+
+    exit_after(print_report(&outcome), exit(&args, &outcome)).into()
+}
 
-    let mut lints = Lints::default();
-    lints
-        .add_lint(lints::mw::mw_has_radar())
-        .add_lint(lints::irmd::irmd_linked_to_by_mw())
-        .add_lint(lints::sam_launcher::sam_launcher_attached_to_radar());
+/// The exit code for the findings of a run.
+fn exit(args: &Args, outcome: &Outcome) -> Exit {
+    let warnings = outcome.linted.iter().flat_map(|l| &l.warnings);
+    let denied = warnings.clone().any(|w| w.severity >= args.deny);
+    let too_many = args.max_warnings.is_some_and(|max| warnings.count() > max);
 
-    for w in lints.lint(&scenario).unwrap() {
-        println!("Lint Warning: {:?}", w);
+    if outcome.linted.iter().any(|l| l.lint_failed) {
+        Exit::InternalError
+    } else if outcome.load_failed > 0 || outcome.campaigns_failed > 0 {
+        Exit::InvalidScenario
+    } else if denied || too_many {
+        Exit::Findings
+    } else {
+        Exit::Clean
     }
 }
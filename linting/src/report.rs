@@ -0,0 +1,74 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::lints::{LintInfo, Warning};
+
+pub mod checkstyle;
+pub mod github;
+pub mod human;
+pub mod json;
+pub mod junit;
+pub mod sarif;
+
+/// Everything a [Reporter] gets to know about one linted scenario.
+pub struct ScenarioReport<'a> {
+    pub path: &'a Path,
+    /// The scenario text [Warning::span]s point into.
+    pub source: &'a str,
+    pub warnings: &'a [Warning],
+}
+
+pub trait Reporter {
+    /// `rules` are the lints enabled for any of the scenarios.
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Human,
+    Json,
+    Sarif,
+    Junit,
+    Checkstyle,
+    Github,
+}
+
+impl Format {
+    pub fn reporter(self) -> Box<dyn Reporter> {
+        match self {
+            Format::Human => Box::new(human::Human),
+            Format::Json => Box::new(json::Json),
+            Format::Sarif => Box::new(sarif::Sarif),
+            Format::Junit => Box::new(junit::JUnit),
+            Format::Checkstyle => Box::new(checkstyle::Checkstyle),
+            Format::Github => Box::new(github::GitHub),
+        }
+    }
+}
+
+/// Escapes text for use in XML attributes and text nodes.
+pub(crate) fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
@@ -0,0 +1,54 @@
+use std::io::{self, Write};
+
+use crate::lints::{LintInfo, Severity};
+
+use super::{xml_escape, Reporter, ScenarioReport};
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "info",
+    }
+}
+
+pub struct Checkstyle;
+
+impl Reporter for Checkstyle {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        _rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(out, r#"<checkstyle version="4.3">"#)?;
+
+        for s in scenarios {
+            writeln!(
+                out,
+                r#"  <file name="{}">"#,
+                xml_escape(&s.path.to_string_lossy())
+            )?;
+
+            for w in s.warnings {
+                let (line, column) = w.span.map_or((0, 0), |s| (s.start_line, s.start_column));
+
+                writeln!(
+                    out,
+                    r#"    <error line="{}" column="{}" severity="{}" message="{}" source="{}.{}"/>"#,
+                    line,
+                    column,
+                    severity(w.severity),
+                    xml_escape(&w.to_string()),
+                    env!("CARGO_PKG_NAME"),
+                    w.lint.name,
+                )?;
+            }
+
+            writeln!(out, "  </file>")?;
+        }
+
+        writeln!(out, "</checkstyle>")
+    }
+}
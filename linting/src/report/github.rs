@@ -0,0 +1,61 @@
+//! GitHub Actions [workflow commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions),
+//! which show up as annotations on the changed files.
+
+use std::io::{self, Write};
+
+use crate::lints::{LintInfo, Severity};
+
+use super::{Reporter, ScenarioReport};
+
+fn escape_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(s: &str) -> String {
+    escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "notice",
+    }
+}
+
+pub struct GitHub;
+
+impl Reporter for GitHub {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        _rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        for s in scenarios {
+            let file = escape_property(&s.path.to_string_lossy());
+
+            for w in s.warnings {
+                let location = match w.span {
+                    Some(span) => format!(
+                        "file={file},line={},col={},endLine={},endColumn={}",
+                        span.start_line, span.start_column, span.end_line, span.end_column
+                    ),
+                    None => format!("file={file}"),
+                };
+
+                writeln!(
+                    out,
+                    "::{} {location},title={}::{}",
+                    command(w.severity),
+                    escape_property(w.lint.code),
+                    escape_data(&format!("{w} [{}]", w.lint.name)),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
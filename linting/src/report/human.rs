@@ -0,0 +1,154 @@
+use std::cmp::Reverse;
+use std::io::{self, Write};
+
+use annotate_snippets::{AnnotationKind, Level, Origin, Renderer, Snippet};
+use anstyle::{AnsiColor, Style};
+
+use crate::lints::{LintInfo, Severity, Warning};
+
+use super::{Reporter, ScenarioReport};
+
+fn style(severity: Severity) -> Style {
+    let color = match severity {
+        Severity::Error => AnsiColor::BrightRed,
+        Severity::Warning => AnsiColor::BrightYellow,
+        Severity::Note => AnsiColor::BrightCyan,
+        Severity::Help => AnsiColor::BrightGreen,
+    };
+
+    Style::new().bold().fg_color(Some(color.into()))
+}
+
+const SEVERITIES: [Severity; 4] = [
+    Severity::Error,
+    Severity::Warning,
+    Severity::Note,
+    Severity::Help,
+];
+
+/// Writes e.g. `2 errors, 1 warning`, skipping severities without findings.
+fn write_counts(out: &mut dyn Write, counts: &[usize; 4]) -> io::Result<()> {
+    let mut first = true;
+
+    for (severity, &count) in SEVERITIES.iter().zip(counts) {
+        if count == 0 {
+            continue;
+        }
+
+        if !first {
+            write!(out, ", ")?;
+        }
+        first = false;
+
+        let plural = if count == 1 { "" } else { "s" };
+        let style = style(*severity);
+        write!(out, "{style}{count} {}{plural}{style:#}", severity.as_str())?;
+    }
+
+    if first {
+        write!(out, "no findings")?;
+    }
+
+    Ok(())
+}
+
+fn level(severity: Severity) -> Level<'static> {
+    match severity {
+        Severity::Error => Level::ERROR,
+        Severity::Warning => Level::WARNING,
+        Severity::Note => Level::NOTE,
+        Severity::Help => Level::HELP,
+    }
+}
+
+/// Renders a warning like rustc does, with the lines it points to.
+fn render(path: &str, source: &str, w: &Warning) -> String {
+    let title = level(w.severity)
+        .primary_title(&w.description)
+        .id(w.lint.code);
+    let note = Level::NOTE.message(format!("`--explain {}` for more", w.lint.name));
+    let label = w.unit.as_ref().map(|u| u.to_string());
+
+    let group = match w.span {
+        Some(span) => {
+            let snippet = Snippet::source(source).path(path).annotation(
+                AnnotationKind::Primary
+                    .span(span.start..span.end)
+                    .label(label),
+            );
+            let snippet = match (&w.related, w.related_span) {
+                (Some(unit), Some(span)) => snippet.annotation(
+                    AnnotationKind::Context
+                        .span(span.start..span.end)
+                        .label(unit.to_string()),
+                ),
+                _ => snippet,
+            };
+
+            title.element(snippet).element(note)
+        }
+        None => {
+            let title = title.element(Origin::path(path));
+            match label {
+                Some(label) => title.element(Level::NOTE.message(label)),
+                None => title,
+            }
+            .element(note)
+        }
+    };
+
+    let group = match w.fix {
+        Some(ref fix) => group.element(Level::HELP.message(format!("{} (`--fix`)", fix.message))),
+        None => group,
+    };
+
+    Renderer::styled().render(&[group])
+}
+
+/// Findings grouped by severity, most severe first, with a summary for each
+/// scenario and one for all of them.
+pub struct Human;
+
+impl Reporter for Human {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        _rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        let bold = Style::new().bold();
+        let mut total = [0; 4];
+
+        for s in scenarios {
+            let path = s.path.display().to_string();
+            // Stable, so findings keep their order within a severity.
+            let mut warnings: Vec<_> = s.warnings.iter().collect();
+            warnings.sort_by_key(|w| Reverse(w.severity));
+
+            let mut counts = [0; 4];
+
+            for w in warnings {
+                counts[SEVERITIES.iter().position(|&s| s == w.severity).unwrap()] += 1;
+
+                writeln!(out, "{}", render(&path, s.source, w))?;
+                writeln!(out)?;
+            }
+
+            write!(out, "{bold}{}{bold:#}: ", s.path.display())?;
+            write_counts(out, &counts)?;
+            writeln!(out)?;
+
+            for (total, count) in total.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+
+        if scenarios.len() > 1 {
+            write!(out, "{bold}{} scenarios{bold:#}: ", scenarios.len())?;
+            write_counts(out, &total)?;
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
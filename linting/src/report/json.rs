@@ -0,0 +1,108 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::lints::{LintInfo, Warning};
+
+use super::{Reporter, ScenarioReport};
+
+/// Version of the JSON document layout, bumped on any breaking change.
+pub const FORMAT_VERSION: u32 = 5;
+
+/// The document printed by `--format json`.
+///
+/// ```json
+/// {
+///   "version": 5,
+///   "scenarios": [
+///     {
+///       "path": "path/to/scenario.vts",
+///       "warnings": [
+///         {
+///           "lint": {
+///             "code": "VTS0001",
+///             "name": "mw-has-radar"
+///           },
+///           "severity": "warning",
+///           "unit": {
+///             "id": 16,
+///             "name": "Mobile Missile Warning Truck",
+///             "unit_type": "MissileWarning",
+///             "faction": "Allied",
+///             "occurrence": 0
+///           },
+///           "field": "radarUnits",
+///           "message": "MW has no Radars!",
+///           "span": {
+///             "start": 14485,
+///             "end": 14497,
+///             "start_line": 570,
+///             "start_column": 5,
+///             "end_line": 570,
+///             "end_column": 17
+///           },
+///           "related": null,
+///           "related_span": null,
+///           "fix": null
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// `unit` is `null` for warnings about the scenario as a whole,
+/// `unit_type` is `null` for units we don't have a type for, and both
+/// `unit_type` and `faction` are `null` for unknown units. `field` and `span`
+/// are `null` if the warning isn't about a specific key, or the unit couldn't
+/// be found in the text. Byte offsets in `span` are 0-based, lines and
+/// columns 1-based, and the end is exclusive.
+///
+/// `occurrence` tells units sharing a `unitInstanceID` apart: 0 for the first
+/// unit with the id, counting up in the order of the scenario. `related` is
+/// another unit the warning points to, e.g. the first unit with a duplicated
+/// id, with the same fields as `unit`, and `related_span` is where it is. Both
+/// are `null` if there's no such unit, `related_span` also if it couldn't be
+/// found in the text.
+///
+/// `fix` is `null` if there's no suggested fix, otherwise e.g.
+/// `{"message": "Remove id:5 from radarUnits", "edits": [{"kind": "remove_id",
+/// "field": "radarUnits", "id": 5}]}`. Edits of kind `set` have a `value`
+/// instead of an `id`.
+#[derive(Serialize)]
+pub struct JsonReport<'a> {
+    pub version: u32,
+    pub scenarios: Vec<JsonScenario<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct JsonScenario<'a> {
+    pub path: &'a Path,
+    pub warnings: &'a [Warning],
+}
+
+pub struct Json;
+
+impl Reporter for Json {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        _rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        let report = JsonReport {
+            version: FORMAT_VERSION,
+            scenarios: scenarios
+                .iter()
+                .map(|s| JsonScenario {
+                    path: s.path,
+                    warnings: s.warnings,
+                })
+                .collect(),
+        };
+
+        serde_json::to_writer_pretty(&mut *out, &report)?;
+        writeln!(out)
+    }
+}
@@ -0,0 +1,82 @@
+use std::io::{self, Write};
+
+use crate::lints::LintInfo;
+
+use super::{xml_escape, Reporter, ScenarioReport};
+
+/// One `<testsuite>` per scenario, containing one `<testcase>` per lint,
+/// which fails if the lint produced any warnings.
+pub struct JUnit;
+
+impl Reporter for JUnit {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        let failures = |s: &ScenarioReport| {
+            rules
+                .iter()
+                .filter(|r| s.warnings.iter().any(|w| w.lint == **r))
+                .count()
+        };
+
+        let total_failures: usize = scenarios.iter().map(failures).sum();
+
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            out,
+            r#"<testsuites name="{}" tests="{}" failures="{}">"#,
+            env!("CARGO_PKG_NAME"),
+            rules.len() * scenarios.len(),
+            total_failures,
+        )?;
+
+        for s in scenarios {
+            let path = xml_escape(&s.path.to_string_lossy());
+
+            writeln!(
+                out,
+                r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+                path,
+                rules.len(),
+                failures(s),
+            )?;
+
+            for rule in rules {
+                let warnings: Vec<_> = s.warnings.iter().filter(|w| w.lint == *rule).collect();
+
+                if warnings.is_empty() {
+                    writeln!(
+                        out,
+                        r#"    <testcase name="{} {}" classname="{}"/>"#,
+                        rule.code, rule.name, path,
+                    )?;
+                    continue;
+                }
+
+                writeln!(
+                    out,
+                    r#"    <testcase name="{} {}" classname="{}">"#,
+                    rule.code, rule.name, path,
+                )?;
+                writeln!(
+                    out,
+                    r#"      <failure message="{} warning(s)" type="{}">"#,
+                    warnings.len(),
+                    rule.code,
+                )?;
+                for w in warnings {
+                    writeln!(out, "{}", xml_escape(&w.to_string()))?;
+                }
+                writeln!(out, "      </failure>")?;
+                writeln!(out, "    </testcase>")?;
+            }
+
+            writeln!(out, "  </testsuite>")?;
+        }
+
+        writeln!(out, "</testsuites>")
+    }
+}
@@ -0,0 +1,240 @@
+//! Minimal [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! emitter, just enough for code-scanning UIs to show our warnings.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::all_rules;
+use crate::lints::{LintInfo, Severity, UnitInfo};
+use crate::source::Span;
+
+use super::{Reporter, ScenarioReport};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver {
+    name: &'static str,
+    version: &'static str,
+    rules: Vec<Rule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: &'static str,
+    name: &'static str,
+    short_description: Message,
+    full_description: Message,
+    default_configuration: Configuration,
+    properties: RuleProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleProperties {
+    category: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: Message,
+    locations: Vec<Location>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<Location>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    name: String,
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+fn path_to_uri(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn location(uri: &str, span: Option<Span>, unit: Option<&UnitInfo>) -> Location {
+    Location {
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: uri.to_string(),
+            },
+            region: span.map(|span| Region {
+                start_line: span.start_line,
+                start_column: span.start_column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+            }),
+        },
+        logical_locations: unit
+            .iter()
+            .map(|u| LogicalLocation {
+                name: u.name.clone(),
+                fully_qualified_name: format!("UNITS/{}", u.id),
+                kind: "object",
+            })
+            .collect(),
+    }
+}
+
+impl Log {
+    /// Describes every lint there is, not only the enabled ones, so tools can
+    /// show the rule of any result they've seen before.
+    pub fn new(scenarios: &[ScenarioReport]) -> Log {
+        let rules = &all_rules();
+
+        let results = scenarios
+            .iter()
+            .flat_map(|s| {
+                let uri = path_to_uri(s.path);
+
+                s.warnings.iter().map(move |w| SarifResult {
+                    rule_id: w.lint.code,
+                    rule_index: rules.iter().position(|r| *r == w.lint),
+                    level: level(w.severity),
+                    message: Message {
+                        text: w.description.clone(),
+                    },
+                    locations: vec![location(&uri, w.span, w.unit.as_ref())],
+                    related_locations: w
+                        .related
+                        .iter()
+                        .map(|u| location(&uri, w.related_span, Some(u)))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        let rules = rules
+            .iter()
+            .map(|r| Rule {
+                id: r.code,
+                name: r.name,
+                short_description: Message {
+                    text: r.summary.to_string(),
+                },
+                full_description: Message {
+                    text: r.explanation.to_string(),
+                },
+                default_configuration: Configuration {
+                    level: level(r.default_severity),
+                },
+                properties: RuleProperties {
+                    category: r.category.as_str(),
+                },
+            })
+            .collect();
+
+        Log {
+            schema: SCHEMA,
+            version: "2.1.0",
+            runs: vec![Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: env!("CARGO_PKG_NAME"),
+                        version: env!("CARGO_PKG_VERSION"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        }
+    }
+}
+
+pub struct Sarif;
+
+impl Reporter for Sarif {
+    fn report(
+        &self,
+        out: &mut dyn Write,
+        _rules: &[&'static LintInfo],
+        scenarios: &[ScenarioReport],
+    ) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, &Log::new(scenarios))?;
+        writeln!(out)
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::lints::{UnitInfo, Warning};
+
+/// A range of the scenario text.
+///
+/// Lines and columns are 1-based, columns count characters. Both `end` and
+/// `end_column` point just past the last character.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Span {
+    /// Byte offset.
+    pub start: usize,
+    /// Byte offset.
+    pub end: usize,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// Where a `UnitSpawner` block is.
+#[derive(Debug)]
+struct SpawnerSource {
+    block: Span,
+    /// The `key = value` lines of the spawner and its `UnitFields`.
+    fields: HashMap<String, Span>,
+}
+
+/// A `UnitSpawner` block we're in the middle of.
+struct OpenSpawner {
+    /// The line of `UnitSpawner`, the block ends at the matching `}`.
+    start: Span,
+    depth: usize,
+    id: Option<i64>,
+    /// Whether the last node opened directly in the spawner is `UnitFields`.
+    in_fields: bool,
+    fields: HashMap<String, Span>,
+}
+
+/// Maps units and fields back to where they are in the scenario text.
+///
+/// [vts_parsing] doesn't keep track of positions, so we find the
+/// `UnitSpawner` blocks by scanning the raw text ourselves.
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    /// In order of appearance, there's more than one if ids are duplicated.
+    spawners: HashMap<i64, Vec<SpawnerSource>>,
+    /// The `key = value` lines at the top level of the scenario.
+    scenario: HashMap<String, Span>,
+}
+
+impl SourceMap {
+    pub fn new(source: &str) -> SourceMap {
+        let mut map = SourceMap::default();
+
+        let mut current: Option<OpenSpawner> = None;
+        let mut depth = 0;
+        let mut offset = 0;
+
+        for (idx, raw_line) in source.split_inclusive('\n').enumerate() {
+            let line_offset = offset;
+            offset += raw_line.len();
+
+            let line = raw_line.trim();
+            let indent = raw_line.len() - raw_line.trim_start().len();
+            let indent_chars = raw_line[..indent].chars().count();
+            let span = Span {
+                start: line_offset + indent,
+                end: line_offset + indent + line.len(),
+                start_line: idx + 1,
+                start_column: indent_chars + 1,
+                end_line: idx + 1,
+                end_column: indent_chars + line.chars().count() + 1,
+            };
+
+            match line {
+                "{" => depth += 1,
+                "}" => {
+                    if current.as_ref().is_some_and(|s| s.depth == depth) {
+                        let spawner = current.take().unwrap();
+                        if let Some(id) = spawner.id {
+                            map.spawners.entry(id).or_default().push(SpawnerSource {
+                                block: Span {
+                                    end: span.end,
+                                    end_line: span.end_line,
+                                    end_column: span.end_column,
+                                    ..spawner.start
+                                },
+                                fields: spawner.fields,
+                            });
+                        }
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                "UnitSpawner" if current.is_none() => {
+                    current = Some(OpenSpawner {
+                        start: span,
+                        depth: depth + 1,
+                        id: None,
+                        in_fields: false,
+                        fields: HashMap::new(),
+                    })
+                }
+                _ => {
+                    let assignment = line.split_once('=').map(|(k, v)| (k.trim(), v.trim()));
+
+                    let Some(ref mut spawner) = current else {
+                        if let (Some((key, _)), 1) = (assignment, depth) {
+                            map.scenario.entry(key.to_string()).or_insert(span);
+                        }
+                        continue;
+                    };
+
+                    match assignment {
+                        None if depth == spawner.depth => spawner.in_fields = line == "UnitFields",
+                        Some((key, value)) if depth == spawner.depth => {
+                            if key == "unitInstanceID" {
+                                spawner.id = value.parse().ok();
+                            }
+                            spawner.fields.entry(key.to_string()).or_insert(span);
+                        }
+                        Some((key, _)) if depth == spawner.depth + 1 && spawner.in_fields => {
+                            spawner.fields.entry(key.to_string()).or_insert(span);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        map
+    }
+
+    /// The first `UnitSpawner` block of the unit with this `unitInstanceID`.
+    pub fn unit(&self, id: i64) -> Option<Span> {
+        self.spawners.get(&id)?.first().map(|s| s.block)
+    }
+
+    /// The `UnitSpawner` of a unit, units with duplicated ids are told apart
+    /// by [UnitInfo::occurrence].
+    fn spawner(&self, unit: &UnitInfo) -> Option<&SpawnerSource> {
+        self.spawners.get(&unit.id)?.get(unit.occurrence)
+    }
+
+    /// The `key = value` line of `field` in `unit`, otherwise its whole
+    /// `UnitSpawner` block.
+    fn locate_unit(&self, unit: &UnitInfo, field: Option<&str>) -> Option<Span> {
+        let spawner = self.spawner(unit)?;
+
+        field
+            .and_then(|f| spawner.fields.get(f).copied())
+            .or(Some(spawner.block))
+    }
+
+    /// The `key = value` line of `field`, in the unit the warning is about or
+    /// the scenario if it's about no unit.
+    pub fn field(&self, warning: &Warning, field: &str) -> Option<Span> {
+        match warning.unit {
+            Some(ref unit) => self.spawner(unit)?.fields.get(field).copied(),
+            None => self.scenario.get(field).copied(),
+        }
+    }
+
+    /// Where a warning points to: the line of [Warning::field] if we can find
+    /// it, otherwise the whole `UnitSpawner` block.
+    pub fn locate(&self, warning: &Warning) -> Option<Span> {
+        match warning.unit {
+            Some(ref unit) => self.locate_unit(unit, warning.field),
+            None => self.scenario.get(warning.field?).copied(),
+        }
+    }
+
+    /// Where [Warning::related] is, the same field as the warning points to
+    /// if the unit has it.
+    pub fn locate_related(&self, warning: &Warning) -> Option<Span> {
+        self.locate_unit(warning.related.as_ref()?, warning.field)
+    }
+}
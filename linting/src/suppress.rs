@@ -0,0 +1,174 @@
+//! Suppressing findings from inside the scenario.
+//!
+//! A `[lint:allow mw-has-radar]` marker in the `unitName` of a unit
+//! suppresses that lint for the unit, the same marker in the
+//! `scenarioDescription` suppresses it for the whole scenario. Multiple lints
+//! can be separated by commas, e.g. `[lint:allow mw-has-radar, VTS0002]`.
+
+use vts_units::Scenario;
+
+use crate::lints::{duplicate_units, find_lint, Category, LintInfo, Severity, UnitInfo, Warning};
+
+const EXPLANATION: &str = "\
+Findings can be suppressed from inside the scenario with a
+`[lint:allow <lint>]` marker, either in the name of a unit, or in the
+scenario description to suppress a lint for the whole scenario.
+
+This lint fires if a marker names a lint which doesn't exist, or if it
+didn't suppress anything, usually because the problem has been fixed since.
+Remove the marker, so it doesn't hide the problem if it comes back.";
+
+pub const UNUSED_SUPPRESSION: LintInfo = LintInfo {
+    code: "VTS0004",
+    name: "unused-suppression",
+    summary: "Suppression markers should suppress something.",
+    explanation: EXPLANATION,
+    default_severity: Severity::Warning,
+    category: Category::Style,
+};
+
+const MARKER: &str = "[lint:allow ";
+
+/// The lint names in all markers in `text`.
+fn marked_lints(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(MARKER) {
+        rest = &rest[start + MARKER.len()..];
+
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+
+        names.extend(
+            rest[..end]
+                .split(',')
+                .map(|n| n.trim())
+                .filter(|n| !n.is_empty()),
+        );
+        rest = &rest[end..];
+    }
+
+    names
+}
+
+struct Suppression {
+    name: String,
+    lint: Option<&'static LintInfo>,
+    /// `None` for suppressions in the `scenarioDescription`.
+    unit: Option<UnitInfo>,
+    used: bool,
+}
+
+impl Suppression {
+    fn matches(&self, warning: &Warning) -> bool {
+        let Some(lint) = self.lint else {
+            return false;
+        };
+
+        lint == warning.lint
+            && self.unit.as_ref().is_none_or(|u| {
+                warning
+                    .unit
+                    .as_ref()
+                    .is_some_and(|w| w.id == u.id && w.occurrence == u.occurrence)
+            })
+    }
+}
+
+pub struct Suppressions {
+    suppressions: Vec<Suppression>,
+}
+
+impl Suppressions {
+    pub fn collect(scenario: &Scenario, rules: &[&'static LintInfo]) -> Suppressions {
+        let mut suppressions = Vec::new();
+
+        let description = scenario
+            .get_value("scenarioDescription")
+            .and_then(|v| v.as_string())
+            .unwrap_or_default();
+
+        for name in marked_lints(description) {
+            suppressions.push(Suppression {
+                name: name.to_string(),
+                lint: find_lint(rules, name),
+                unit: None,
+                used: false,
+            });
+        }
+
+        let units = scenario
+            .units()
+            .values()
+            .map(|u| (u.name(), UnitInfo::from(u)));
+        let unknown_units = scenario
+            .unknown_units()
+            .values()
+            .map(|u| (u.name(), UnitInfo::from(u)));
+        let duplicates = duplicate_units(scenario)
+            .into_iter()
+            .map(|(u, info)| (u.name(), info));
+
+        for (unit_name, unit) in units.chain(unknown_units).chain(duplicates) {
+            for name in marked_lints(unit_name) {
+                suppressions.push(Suppression {
+                    name: name.to_string(),
+                    lint: find_lint(rules, name),
+                    unit: Some(unit.clone()),
+                    used: false,
+                });
+            }
+        }
+
+        Suppressions { suppressions }
+    }
+
+    /// Removes suppressed warnings.
+    pub fn apply(&mut self, warnings: &mut Vec<Warning>) {
+        warnings.retain(|w| {
+            let mut suppressed = false;
+
+            for s in self.suppressions.iter_mut().filter(|s| s.matches(w)) {
+                s.used = true;
+                suppressed = true;
+            }
+
+            !suppressed
+        });
+    }
+
+    /// Warnings for suppressions of unknown lints, and suppressions which
+    /// didn't suppress anything. Suppressions of lints which aren't in
+    /// `enabled` are ignored, they can't have been used.
+    pub fn unused(&self, enabled: &[&'static LintInfo]) -> Vec<Warning> {
+        self.suppressions
+            .iter()
+            .filter_map(|s| {
+                let place = match s.unit {
+                    Some(_) => "unit name",
+                    None => "scenario description",
+                };
+
+                let description = match s.lint {
+                    None => format!("Unknown lint {} suppressed in {place}.", s.name),
+                    Some(lint) if !s.used && enabled.contains(&lint) => {
+                        format!("Suppression of {} in {place} is unused.", lint.name)
+                    }
+                    Some(_) => return None,
+                };
+
+                let field = match s.unit {
+                    Some(_) => "unitName",
+                    None => "scenarioDescription",
+                };
+
+                Some(
+                    Warning::new(&UNUSED_SUPPRESSION, s.unit.clone(), description)
+                        .with_field(field),
+                )
+            })
+            .collect()
+    }
+}
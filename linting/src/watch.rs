@@ -0,0 +1,169 @@
+//! `--watch`, linting again whenever a scenario, campaign or config changes.
+//!
+//! We poll modification times instead of subscribing to file system events,
+//! the in-game editor saves rarely enough for that to be plenty.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use anstyle::{AnsiColor, Style};
+
+use vts_linting::config::{self, Config};
+use vts_linting::lints::Severity;
+
+use crate::Linted;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn is_watched(path: &Path) -> bool {
+    let extension = path.extension().and_then(|e| e.to_str());
+
+    matches!(extension, Some(e) if e.eq_ignore_ascii_case("vts") || e.eq_ignore_ascii_case("vtc"))
+        || path.file_name().is_some_and(|n| n == config::FILE_NAME)
+}
+
+/// Modification times of everything which can change the findings.
+pub struct Snapshot {
+    paths: Vec<PathBuf>,
+    config: Option<PathBuf>,
+    files: BTreeMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf], config: Option<&Path>) -> Snapshot {
+        let mut files = BTreeMap::new();
+
+        for path in paths {
+            add_files(path, &mut files);
+
+            if let Some(config) = Config::discover(path) {
+                add_file(&config, &mut files);
+            }
+        }
+
+        if let Some(config) = config {
+            add_file(config, &mut files);
+        }
+
+        Snapshot {
+            paths: paths.to_vec(),
+            config: config.map(Path::to_path_buf),
+            files,
+        }
+    }
+
+    /// Blocks until any watched file is changed, added or removed.
+    pub fn wait_for_change(&self) {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            if Snapshot::take(&self.paths, self.config.as_deref()).files != self.files {
+                return;
+            }
+        }
+    }
+}
+
+fn add_file(path: &Path, files: &mut BTreeMap<PathBuf, Option<SystemTime>>) {
+    let modified = path.metadata().and_then(|m| m.modified()).ok();
+    files.insert(path.to_path_buf(), modified);
+}
+
+/// `path` itself, and if it's a directory every watched file below it.
+fn add_files(path: &Path, files: &mut BTreeMap<PathBuf, Option<SystemTime>>) {
+    add_file(path, files);
+
+    let Ok(entries) = path.read_dir() else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let entry = entry.path();
+
+        if entry.is_dir() {
+            add_files(&entry, files);
+        } else if is_watched(&entry) {
+            add_file(&entry, files);
+        }
+    }
+}
+
+/// A finding, as compared between runs.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Finding {
+    path: PathBuf,
+    severity: Severity,
+    code: &'static str,
+    text: String,
+}
+
+/// The findings of a run, to tell which are new in the next one.
+pub struct Findings(BTreeSet<Finding>);
+
+impl Findings {
+    pub(crate) fn new(linted: &[Linted]) -> Findings {
+        Findings(
+            linted
+                .iter()
+                .flat_map(|l| {
+                    l.warnings.iter().map(|w| Finding {
+                        path: l.path.clone(),
+                        severity: w.severity,
+                        code: w.lint.code,
+                        text: w.to_string(),
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+pub fn clear_screen() {
+    // Stripped by anstream if stdout isn't a terminal.
+    let mut out = anstream::stdout().lock();
+    let _ = write!(out, "\x1b[2J\x1b[H");
+    let _ = out.flush();
+}
+
+/// Lists findings which are new or resolved since `previous`.
+pub fn print_changes(
+    out: &mut dyn Write,
+    previous: &Findings,
+    current: &Findings,
+) -> io::Result<()> {
+    let new = Style::new().fg_color(Some(AnsiColor::BrightRed.into()));
+    let resolved = Style::new().fg_color(Some(AnsiColor::BrightGreen.into()));
+
+    let added: Vec<_> = current.0.difference(&previous.0).collect();
+    let removed: Vec<_> = previous.0.difference(&current.0).collect();
+
+    if added.is_empty() && removed.is_empty() {
+        writeln!(out, "No changes since the last run.")?;
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "Since the last run: {new}{} new{new:#}, {resolved}{} resolved{resolved:#}",
+        added.len(),
+        removed.len()
+    )?;
+
+    for (style, sign, findings) in [(new, '+', added), (resolved, '-', removed)] {
+        for f in findings {
+            writeln!(
+                out,
+                "{style}{sign} {}[{}] {}: {}{style:#}",
+                f.severity.as_str(),
+                f.code,
+                f.path.display(),
+                f.text
+            )?;
+        }
+    }
+
+    Ok(())
+}
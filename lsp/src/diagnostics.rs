@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Range};
+use vts_linting::config::Config;
+use vts_linting::lints::{LintInfo, Severity};
+use vts_linting::{lint_scenario, Setup};
+use vts_parsing::parse::try_parse;
+use vts_units::Scenario;
+
+use crate::position::range;
+
+const SOURCE: &str = "vts-lint";
+
+fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Note => DiagnosticSeverity::INFORMATION,
+        Severity::Help => DiagnosticSeverity::HINT,
+    }
+}
+
+/// A problem with the whole document, shown at its start.
+fn document_error(message: String) -> Diagnostic {
+    Diagnostic {
+        range: Range::default(),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some(SOURCE.to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Lints a scenario, `path` is used to find its `vts-lint.toml`.
+pub fn diagnostics(
+    path: Option<&Path>,
+    text: &str,
+    rules: &[&'static LintInfo],
+) -> Vec<Diagnostic> {
+    let parsed = match try_parse(text) {
+        Ok(parsed) => parsed,
+        Err(e) => return vec![document_error(format!("Couldn't parse scenario:\n{e}"))],
+    };
+
+    let scenario = match Scenario::from_node_lenient(&parsed) {
+        Ok(scenario) => scenario,
+        Err(e) => return vec![document_error(format!("Invalid scenario: {e}"))],
+    };
+
+    let mut diagnostics = Vec::new();
+
+    let config = match path.and_then(Config::discover) {
+        Some(config) => Config::load(&config, rules).unwrap_or_else(|e| {
+            diagnostics.push(document_error(e.to_string()));
+            Config::default()
+        }),
+        None => Config::default(),
+    };
+
+    let setup = Setup::new(config, rules, &[], &[]);
+    let findings = lint_scenario(&scenario, text, &setup, rules, vec![]);
+
+    diagnostics.extend(findings.warnings.iter().map(|w| Diagnostic {
+        range: w.span.map(|s| range(text, s)).unwrap_or_default(),
+        severity: Some(severity(w.severity)),
+        code: Some(NumberOrString::String(w.lint.code.to_string())),
+        source: Some(SOURCE.to_string()),
+        message: format!("{w} [{}]", w.lint.name),
+        ..Default::default()
+    }));
+
+    diagnostics
+}
@@ -0,0 +1,199 @@
+//! A language server for `.vts` files, publishing the findings of vts-lint as
+//! diagnostics, with hover info and go-to-definition for unit ids.
+//!
+//! Talks LSP over stdin and stdout.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{GotoDefinition, HoverRequest, Request as _};
+use lsp_types::{
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+    HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+use vts_linting::all_rules;
+use vts_linting::lints::LintInfo;
+
+mod diagnostics;
+mod navigation;
+mod position;
+
+/// The file a `file://` uri points to, to find its config.
+fn file_path(uri: &Uri) -> Option<PathBuf> {
+    if uri.scheme()?.as_str() != "file" {
+        return None;
+    }
+
+    let path = uri.path().as_estr().decode().into_string_lossy();
+
+    // `/C:/...` on Windows.
+    match path.strip_prefix('/') {
+        Some(rest) if cfg!(windows) => Some(PathBuf::from(rest)),
+        _ => Some(PathBuf::from(path.as_ref())),
+    }
+}
+
+struct Server {
+    connection: Connection,
+    rules: Vec<&'static LintInfo>,
+    /// The text of every open document.
+    documents: HashMap<Uri, String>,
+}
+
+impl Server {
+    fn publish_diagnostics(&self, uri: Uri, version: Option<i32>) -> Result<(), Box<dyn Error>> {
+        let diagnostics = match self.documents.get(&uri) {
+            Some(text) => diagnostics::diagnostics(file_path(&uri).as_deref(), text, &self.rules),
+            None => vec![],
+        };
+
+        let params = PublishDiagnosticsParams::new(uri, diagnostics, version);
+        self.connection
+            .sender
+            .send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())?;
+
+        Ok(())
+    }
+
+    fn notification(&mut self, notification: Notification) -> Result<(), Box<dyn Error>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+
+                self.documents.insert(document.uri.clone(), document.text);
+                self.publish_diagnostics(document.uri, Some(document.version))?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+
+                // We only ask for full syncs, so the last change is the text.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(document.uri.clone(), change.text);
+                }
+                self.publish_diagnostics(document.uri, Some(document.version))?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+
+                self.documents.remove(&uri);
+                self.publish_diagnostics(uri, None)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let document = params.text_document_position_params;
+        let text = self.documents.get(&document.text_document.uri)?;
+        let offset = position::offset(text, document.position)?;
+
+        let (contents, range) = navigation::hover(text, offset)?;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: contents,
+            }),
+            range: Some(lsp_types::Range::new(
+                position::position(text, range.start),
+                position::position(text, range.end),
+            )),
+        })
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let document = params.text_document_position_params;
+        let uri = document.text_document.uri;
+        let text = self.documents.get(&uri)?;
+        let offset = position::offset(text, document.position)?;
+
+        let span = navigation::definition(text, offset)?;
+
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            uri,
+            position::range(text, span),
+        )))
+    }
+
+    fn request(&self, request: Request) -> Result<(), Box<dyn Error>> {
+        let response = match request.method.as_str() {
+            HoverRequest::METHOD => {
+                let (id, params) = request.extract::<HoverParams>(HoverRequest::METHOD)?;
+                Response::new_ok(id, self.hover(params))
+            }
+            GotoDefinition::METHOD => {
+                let (id, params) =
+                    request.extract::<GotoDefinitionParams>(GotoDefinition::METHOD)?;
+                Response::new_ok(id, self.definition(params))
+            }
+            _ => method_not_found(request.id, &request.method),
+        };
+
+        self.connection.sender.send(response.into())?;
+
+        Ok(())
+    }
+
+    fn run(mut self) -> Result<(), Box<dyn Error>> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.request(request)?;
+                }
+                Message::Notification(notification) => self.notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn method_not_found(id: RequestId, method: &str) -> Response {
+    Response::new_err(
+        id,
+        lsp_server::ErrorCode::MethodNotFound as i32,
+        format!("Unsupported request {method}."),
+    )
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+
+    let server = Server {
+        connection,
+        rules: all_rules(),
+        documents: HashMap::new(),
+    };
+    server.run()?;
+
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! Hover and go-to-definition on unit ids.
+
+use std::ops::Range;
+use std::str::FromStr;
+
+use vts_parsing::parse::try_parse;
+use vts_units::{get_unit_faction, get_unit_type, Scenario, Unit};
+
+use vts_linting::source::{SourceMap, Span};
+
+/// Fields holding a `;` separated list of `unitInstanceID`s.
+const ID_LISTS: [&str; 5] = [
+    "radarUnits",
+    "decoyUnits",
+    "defenseUnits",
+    "jammerUnits",
+    "unitsToDefend",
+];
+
+/// The value under the cursor, in a `key = value` line.
+pub struct Token<'a> {
+    pub key: &'a str,
+    pub value: &'a str,
+    /// Byte range of `value`.
+    pub range: Range<usize>,
+}
+
+impl<'a> Token<'a> {
+    /// The value at `offset`, or for id lists the id at `offset`.
+    pub fn at(text: &'a str, offset: usize) -> Option<Token<'a>> {
+        let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+        let line = &text[line_start..line_end];
+
+        let (key, value) = line.split_once('=')?;
+        let value_start = line_start + key.len() + 1;
+        let key = key.trim();
+
+        let mut start = value_start + (value.len() - value.trim_start().len());
+        let mut end = value_start + value.trim_end().len();
+
+        if !(start..=end).contains(&offset) {
+            return None;
+        }
+
+        if ID_LISTS.contains(&key) {
+            start += text[start..offset].rfind(';').map_or(0, |i| i + 1);
+            end = text[offset..end].find(';').map_or(end, |i| offset + i);
+        }
+
+        if start == end {
+            return None;
+        }
+
+        Some(Token {
+            key,
+            value: &text[start..end],
+            range: start..end,
+        })
+    }
+
+    /// The `unitInstanceID` this token refers to, if it's one.
+    fn instance_id(&self) -> Option<i64> {
+        if self.key != "unitInstanceID" && !ID_LISTS.contains(&self.key) {
+            return None;
+        }
+
+        self.value.parse().ok()
+    }
+}
+
+fn describe_unit_id(unit_id: &str) -> String {
+    let Ok(unit) = Unit::from_str(unit_id) else {
+        return format!("**{unit_id}**\n\nNot a unit known to vts-lint.");
+    };
+
+    let unit_type = match get_unit_type(&unit) {
+        Some(t) => format!("{t:?}"),
+        None => "none".to_string(),
+    };
+
+    format!(
+        "**{unit_id}**\n\nFaction: {:?}\n\nType: {unit_type}",
+        get_unit_faction(&unit)
+    )
+}
+
+fn describe_instance(text: &str, id: i64) -> String {
+    let node = try_parse(text).ok();
+    let scenario = node
+        .as_ref()
+        .and_then(|n| Scenario::from_node_lenient(n).ok());
+
+    let Some(scenario) = scenario else {
+        return format!("Unit {id}\n\nThe scenario couldn't be read.");
+    };
+
+    if let Some(unit) = scenario.units().get(&id) {
+        let unit_type = match unit.unit_type() {
+            Some(t) => format!("{t:?}"),
+            None => "none".to_string(),
+        };
+
+        format!(
+            "**{}** (id:{id})\n\n`{:?}`, faction: {:?}, type: {unit_type}",
+            unit.name(),
+            unit.unit(),
+            unit.faction()
+        )
+    } else if let Some(unit) = scenario.unknown_units().get(&id) {
+        format!(
+            "**{}** (id:{id})\n\n`{}`, not a unit known to vts-lint.",
+            unit.name(),
+            unit.unit_id()
+        )
+    } else {
+        format!("Unit {id}\n\nNo unit has this id.")
+    }
+}
+
+/// Markdown describing the unit at `offset`, and the range it's about.
+pub fn hover(text: &str, offset: usize) -> Option<(String, Range<usize>)> {
+    let token = Token::at(text, offset)?;
+
+    let contents = if token.key == "unitID" {
+        describe_unit_id(token.value)
+    } else {
+        describe_instance(text, token.instance_id()?)
+    };
+
+    Some((contents, token.range))
+}
+
+/// The `UnitSpawner` an id in an id list at `offset` refers to.
+pub fn definition(text: &str, offset: usize) -> Option<Span> {
+    let token = Token::at(text, offset)?;
+
+    if !ID_LISTS.contains(&token.key) {
+        return None;
+    }
+
+    SourceMap::new(text).unit(token.instance_id()?)
+}
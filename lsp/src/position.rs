@@ -0,0 +1,38 @@
+//! Conversions between our byte offsets and LSP positions, which count
+//! UTF-16 code units.
+
+use lsp_types::{Position, Range};
+use vts_linting::source::Span;
+
+pub fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = before[line_start..].encode_utf16().count();
+
+    Position::new(line as u32, character as u32)
+}
+
+pub fn range(text: &str, span: Span) -> Range {
+    Range::new(position(text, span.start), position(text, span.end))
+}
+
+/// The byte offset of `position`, clamped to the end of its line.
+pub fn offset(text: &str, position: Position) -> Option<usize> {
+    let line_start = match position.line {
+        0 => 0,
+        line => text.match_indices('\n').nth(line as usize - 1)?.0 + 1,
+    };
+
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+
+    let mut units = 0;
+    for (idx, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return Some(line_start + idx);
+        }
+        units += c.len_utf16();
+    }
+
+    Some(line_start + line.len())
+}
@@ -0,0 +1,45 @@
+use vts_parsing::{Node, Value};
+
+/// A campaign (`.vtc`), which bundles the scenarios in the subdirectories
+/// next to it.
+pub struct Campaign<'a> {
+    node: &'a Node,
+    id: String,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ToCampaignError {
+    #[error("Not CAMPAIGN")]
+    NotCampaign,
+    #[error("campaignID missing or invalid")]
+    InvalidCampaignID,
+}
+
+impl<'a> TryFrom<&'a Node> for Campaign<'a> {
+    type Error = ToCampaignError;
+
+    fn try_from(node: &'a Node) -> Result<Campaign<'a>, ToCampaignError> {
+        if node.name != "CAMPAIGN" {
+            return Err(ToCampaignError::NotCampaign);
+        }
+
+        let id = node
+            .values
+            .get("campaignID")
+            .and_then(|v| v.as_string_cvt())
+            .ok_or(ToCampaignError::InvalidCampaignID)?
+            .into_owned();
+
+        Ok(Campaign { node, id })
+    }
+}
+
+impl<'a> Campaign<'a> {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn get_value(&self, k: &str) -> Option<&Value> {
+        self.node.values.get(k)
+    }
+}
@@ -0,0 +1,239 @@
+//! Editing scenarios in place.
+//!
+//! [Scenario](crate::Scenario) and [UnitRef](crate::UnitRef) only read the
+//! parsed [Node], [ScenarioMut] and [UnitMut] change it. Write the result
+//! with [to_vts](crate::write::to_vts).
+
+use vts_parsing::{Node, Value};
+
+use crate::fields::{instance_id_list_value, parse_instance_id_list, AccessFieldsError, IdList};
+use crate::{get_unit, get_unit_type, ToScenarioError, Unit, UnitType};
+
+/// The `unitInstanceID` of a `UnitSpawner`, if it has a valid one.
+fn spawner_id(spawner: &Node) -> Option<i64> {
+    spawner
+        .values
+        .get("unitInstanceID")?
+        .as_number()
+        .filter(|id| *id >= 0)
+}
+
+pub struct ScenarioMut<'a> {
+    node: &'a mut Node,
+}
+
+impl<'a> TryFrom<&'a mut Node> for ScenarioMut<'a> {
+    type Error = ToScenarioError;
+
+    fn try_from(node: &'a mut Node) -> Result<ScenarioMut<'a>, ToScenarioError> {
+        if node.name != "CustomScenario" {
+            return Err(ToScenarioError::NotCustomScenario);
+        }
+
+        if node.get_node("UNITS").is_none() {
+            return Err(ToScenarioError::UnitsMissing);
+        }
+
+        Ok(ScenarioMut { node })
+    }
+}
+
+impl ScenarioMut<'_> {
+    fn units(&self) -> &Node {
+        self.node.get_node("UNITS").expect("checked on creation")
+    }
+
+    fn units_mut(&mut self) -> &mut Node {
+        self.node
+            .get_node_mut("UNITS")
+            .expect("checked on creation")
+    }
+
+    /// The `unitInstanceID`s of all units, in the order they're in.
+    pub fn unit_ids(&self) -> Vec<i64> {
+        self.units().nodes().filter_map(spawner_id).collect()
+    }
+
+    /// The first unit with this `unitInstanceID`.
+    pub fn unit(&mut self, id: i64) -> Option<UnitMut<'_>> {
+        self.units_mut()
+            .nodes_mut()
+            .find(|n| spawner_id(n) == Some(id))
+            .map(|node| UnitMut { id, node })
+    }
+
+    /// The `unitInstanceID` the next added unit gets, one more than the
+    /// highest in use.
+    pub fn next_unit_id(&self) -> i64 {
+        self.unit_ids().into_iter().max().map_or(0, |id| id + 1)
+    }
+
+    /// Adds a `UnitSpawner`, e.g. a copy of another unit's, giving it the
+    /// next free `unitInstanceID`.
+    pub fn add_unit(&mut self, mut spawner: Node) -> UnitMut<'_> {
+        let id = self.next_unit_id();
+        spawner
+            .values
+            .insert("unitInstanceID".to_string(), Value::Number(id));
+
+        let units = self.units_mut();
+        units.nodes.push(spawner);
+
+        UnitMut {
+            id,
+            node: units.nodes.last_mut().expect("just pushed"),
+        }
+    }
+
+    /// Removes the first unit with this `unitInstanceID`, and its id from the
+    /// id lists of the other units.
+    pub fn remove_unit(&mut self, id: i64) -> Option<Node> {
+        let units = self.units_mut();
+        let idx = units.nodes().position(|n| spawner_id(n) == Some(id))?;
+        let removed = units.nodes.remove(idx);
+
+        for node in units.nodes_mut() {
+            let Some(other) = spawner_id(node) else {
+                continue;
+            };
+
+            let mut unit = UnitMut { id: other, node };
+            for list in IdList::ALL {
+                // Lists we can't read are left alone.
+                let _ = unit.remove_instance_id(list, id);
+            }
+        }
+
+        Some(removed)
+    }
+}
+
+/// A `UnitSpawner` of a [ScenarioMut].
+pub struct UnitMut<'a> {
+    id: i64,
+    node: &'a mut Node,
+}
+
+impl UnitMut<'_> {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// `None` if the `unitID` isn't a unit we know.
+    pub fn unit(&self) -> Option<Unit> {
+        get_unit(self.node)
+    }
+
+    pub fn unit_type(&self) -> Option<UnitType> {
+        get_unit_type(&self.unit()?)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.node.values.get("unitName")?.as_string()
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.node
+            .values
+            .insert("unitName".to_string(), Value::String(name.to_string()));
+    }
+
+    pub fn position(&self) -> Option<[f64; 3]> {
+        self.node.values.get("globalPosition")?.as_vector()
+    }
+
+    /// Moves the unit, its `lastValidPlacement` moves along like it does in
+    /// the editor.
+    pub fn set_position(&mut self, position: [f64; 3]) {
+        for key in ["globalPosition", "lastValidPlacement"] {
+            self.node
+                .values
+                .insert(key.to_string(), Value::Vector(position));
+        }
+    }
+
+    /// The raw `UnitSpawner` node.
+    pub fn node(&self) -> &Node {
+        self.node
+    }
+
+    pub fn get_field(&self, k: &str) -> Option<&Value> {
+        self.node.get_node("UnitFields")?.values.get(k)
+    }
+
+    /// Sets a value in `UnitFields`, adding it if it's missing.
+    pub fn set_field(&mut self, k: &str, value: Value) {
+        if self.node.get_node("UnitFields").is_none() {
+            self.node.nodes.push(Node::new(
+                "UnitFields".to_string(),
+                Default::default(),
+                vec![],
+            ));
+        }
+
+        let fields = self.node.get_node_mut("UnitFields").expect("just added");
+        fields.values.insert(k.to_string(), value);
+    }
+
+    /// Fails unless units of our type have `list`.
+    fn check_list(&self, list: IdList) -> Result<(), AccessFieldsError> {
+        let unit_type = self.unit_type().ok_or(AccessFieldsError::UnitMissingType)?;
+
+        match list.applies_to(unit_type) {
+            true => Ok(()),
+            false => Err(AccessFieldsError::InvalidUnitType),
+        }
+    }
+
+    pub fn instance_ids(&self, list: IdList) -> Result<Vec<i64>, AccessFieldsError> {
+        self.check_list(list)?;
+
+        let field = list.field();
+        let value = self
+            .get_field(field)
+            .ok_or(AccessFieldsError::MissingFieldError { field })?;
+
+        parse_instance_id_list(value, field)
+    }
+
+    pub fn set_instance_ids(&mut self, list: IdList, ids: &[i64]) -> Result<(), AccessFieldsError> {
+        self.check_list(list)?;
+
+        self.set_field(list.field(), instance_id_list_value(ids));
+        Ok(())
+    }
+
+    /// Adds `id` to the end of `list`, unless it's already in it.
+    pub fn add_instance_id(&mut self, list: IdList, id: i64) -> Result<(), AccessFieldsError> {
+        let mut ids = self.instance_ids(list)?;
+
+        if !ids.contains(&id) {
+            ids.push(id);
+            self.set_instance_ids(list, &ids)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes `id` from `list`, returns whether it was in it.
+    pub fn remove_instance_id(&mut self, list: IdList, id: i64) -> Result<bool, AccessFieldsError> {
+        let mut ids = self.instance_ids(list)?;
+        let len = ids.len();
+        ids.retain(|i| *i != id);
+
+        if ids.len() == len {
+            return Ok(false);
+        }
+
+        self.set_instance_ids(list, &ids)?;
+        Ok(true)
+    }
+
+    /// Sets `engageEnemies`, which every unit with a type has.
+    pub fn set_engage_enemies(&mut self, engage_enemies: bool) -> Result<(), AccessFieldsError> {
+        self.unit_type().ok_or(AccessFieldsError::UnitMissingType)?;
+
+        self.set_field("engageEnemies", Value::Boolean(engage_enemies));
+        Ok(())
+    }
+}
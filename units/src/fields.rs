@@ -1,6 +1,7 @@
 use vts_parsing::Value;
 
-use crate::UnitRef;
+use crate::write::format_value;
+use crate::{UnitRef, UnitType};
 
 pub enum Fields {
     SAMRadar {
@@ -21,6 +22,70 @@ pub enum Fields {
     IRMD {
         engage_enemies: bool,
     },
+    /// AI aircraft. Fields only some aircraft have are `None` if they're
+    /// missing, references are `None` if they aren't set.
+    Aircraft {
+        engage_enemies: bool,
+        /// e.g. `Orbit`, `Path` or `Parked`.
+        default_behavior: String,
+        /// In knots, like all speeds.
+        initial_speed: f64,
+        default_nav_speed: f64,
+        /// The waypoint to orbit.
+        default_orbit_point: Option<i64>,
+        default_path: Option<i64>,
+        /// In meters.
+        orbit_altitude: f64,
+        /// In percent.
+        fuel: f64,
+        auto_refuel: bool,
+        auto_rtb: bool,
+        rtb_destination: Option<String>,
+        /// e.g. `FlightReady` or `Cold`.
+        parked_start_mode: String,
+        default_radar_enabled: Option<bool>,
+        allow_jamming_at_will: Option<bool>,
+        voice_profile: Option<String>,
+        /// e.g. `Unit_Group_Only`.
+        player_commands_mode: Option<String>,
+    },
+    /// Vehicles and infantry which can move on the ground. Fields only some
+    /// of them have are `None` if they're missing.
+    GroundUnit {
+        engage_enemies: bool,
+        /// e.g. `Parked`, `Move_To_Waypoint` or `Path`.
+        behavior: String,
+        waypoint: Option<i64>,
+        /// e.g. `Slow_10`, infantry doesn't have it.
+        move_speed: Option<String>,
+        default_path: Option<i64>,
+        stop_to_engage: Option<bool>,
+        unit_group: Option<String>,
+        /// e.g. `Default` or `Force_Detected`.
+        detection_mode: String,
+    },
+    Ship {
+        engage_enemies: bool,
+        /// e.g. `Parked`, `Move_To_Waypoint` or `Path`.
+        default_behavior: String,
+        default_waypoint: Option<i64>,
+        default_path: Option<i64>,
+        /// Only allied ships have it.
+        hull_number: Option<i64>,
+    },
+    /// Ships which aircraft can spawn on.
+    Carrier {
+        engage_enemies: bool,
+        default_behavior: String,
+        default_waypoint: Option<i64>,
+        default_path: Option<i64>,
+        hull_number: Option<i64>,
+        /// The frequency of the LSO in MHz, only allied carriers have it.
+        lso_freq: Option<f64>,
+        /// The aircraft spawning on the carrier, as the game wrote it. Kept
+        /// raw until we've seen it filled in by the editor.
+        carrier_spawns: String,
+    },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -44,6 +109,18 @@ pub enum AccessFieldsError {
     UnitMissingType,
 }
 
+impl AccessFieldsError {
+    /// The field which couldn't be accessed, if the error is about one.
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            AccessFieldsError::MissingFieldError { field }
+            | AccessFieldsError::FieldInvalidType { field, .. }
+            | AccessFieldsError::ParseFieldError { field, .. } => Some(field),
+            AccessFieldsError::InvalidUnitType | AccessFieldsError::UnitMissingType => None,
+        }
+    }
+}
+
 fn get_unit_field<'a>(
     unit: &'a UnitRef<'a>,
     field: &'static str,
@@ -52,25 +129,160 @@ fn get_unit_field<'a>(
         .ok_or(AccessFieldsError::MissingFieldError { field })
 }
 
-fn get_engage_enemies(unit: &UnitRef) -> Result<bool, AccessFieldsError> {
-    let engage_enemies = get_unit_field(unit, "engageEnemies")?;
-    let engage_enemies = engage_enemies
-        .as_bool()
+fn parse_bool(value: &Value, field: &'static str) -> Result<bool, AccessFieldsError> {
+    value.as_bool().ok_or(AccessFieldsError::FieldInvalidType {
+        field,
+        expected: "bool",
+        found: value.get_type(),
+    })
+}
+
+fn parse_number(value: &Value, field: &'static str) -> Result<i64, AccessFieldsError> {
+    value
+        .as_number()
+        .ok_or(AccessFieldsError::FieldInvalidType {
+            field,
+            expected: "number",
+            found: value.get_type(),
+        })
+}
+
+/// Numbers without a fractional part are parsed as integers, so accept both.
+fn parse_float(value: &Value, field: &'static str) -> Result<f64, AccessFieldsError> {
+    value
+        .as_float_cvt()
+        .ok_or(AccessFieldsError::FieldInvalidType {
+            field,
+            expected: "float",
+            found: value.get_type(),
+        })
+}
+
+/// A name, like the variants of the game's enums.
+fn parse_string(value: &Value, field: &'static str) -> Result<String, AccessFieldsError> {
+    value
+        .as_string()
+        .map(str::to_string)
+        .ok_or(AccessFieldsError::FieldInvalidType {
+            field,
+            expected: "string",
+            found: value.get_type(),
+        })
+}
+
+/// Whether the game wrote a reference which isn't set, as `null` or nothing.
+fn is_unset(value: &Value) -> bool {
+    matches!(value, Value::Null) || value.as_string() == Some("null")
+}
+
+/// A reference by id, e.g. to a waypoint or path.
+fn parse_optional_id(value: &Value, field: &'static str) -> Result<Option<i64>, AccessFieldsError> {
+    if is_unset(value) {
+        return Ok(None);
+    }
+
+    let id = value
+        .as_number()
         .ok_or(AccessFieldsError::FieldInvalidType {
-            field: "engageEnemies",
-            expected: "bool",
-            found: engage_enemies.get_type(),
+            field,
+            expected: "number",
+            found: value.get_type(),
         })?;
 
-    Ok(engage_enemies)
+    if id < 0 {
+        return Err(AccessFieldsError::ParseFieldError {
+            field,
+            reason: "negative id",
+        });
+    }
+
+    Ok(Some(id))
 }
 
-fn get_instance_id_list_field(
+fn parse_optional_string(
+    value: &Value,
+    field: &'static str,
+) -> Result<Option<String>, AccessFieldsError> {
+    if is_unset(value) {
+        return Ok(None);
+    }
+
+    value
+        .as_string_cvt()
+        .map(|s| Some(s.into_owned()))
+        .ok_or(AccessFieldsError::FieldInvalidType {
+            field,
+            expected: "string",
+            found: value.get_type(),
+        })
+}
+
+fn get_field_with<T>(
     unit: &UnitRef,
     field: &'static str,
-) -> Result<Vec<i64>, AccessFieldsError> {
-    let raw_list = get_unit_field(unit, field)?;
+    parse: fn(&Value, &'static str) -> Result<T, AccessFieldsError>,
+) -> Result<T, AccessFieldsError> {
+    parse(get_unit_field(unit, field)?, field)
+}
+
+/// Like [get_field_with], for fields only some units of a type have.
+fn get_optional_field_with<T>(
+    unit: &UnitRef,
+    field: &'static str,
+    parse: fn(&Value, &'static str) -> Result<T, AccessFieldsError>,
+) -> Result<Option<T>, AccessFieldsError> {
+    unit.get_field(field).map(|v| parse(v, field)).transpose()
+}
 
+fn get_engage_enemies(unit: &UnitRef) -> Result<bool, AccessFieldsError> {
+    get_field_with(unit, "engageEnemies", parse_bool)
+}
+
+/// The fields of a unit which list other units by their `unitInstanceID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdList {
+    Radars,
+    Decoys,
+    MissileDefenses,
+    Jammers,
+    UnitsToDefend,
+}
+
+impl IdList {
+    pub const ALL: [IdList; 5] = [
+        IdList::Radars,
+        IdList::Decoys,
+        IdList::MissileDefenses,
+        IdList::Jammers,
+        IdList::UnitsToDefend,
+    ];
+
+    /// The key in `UnitFields`.
+    pub fn field(self) -> &'static str {
+        match self {
+            IdList::Radars => "radarUnits",
+            IdList::Decoys => "decoyUnits",
+            IdList::MissileDefenses => "defenseUnits",
+            IdList::Jammers => "jammerUnits",
+            IdList::UnitsToDefend => "unitsToDefend",
+        }
+    }
+
+    /// Whether units of this type have the list.
+    pub fn applies_to(self, unit_type: UnitType) -> bool {
+        match unit_type {
+            UnitType::SAMLauncher => self == IdList::Radars,
+            UnitType::MissileWarning => true,
+            _ => false,
+        }
+    }
+}
+
+/// Parses a list of `unitInstanceID`s, which the game writes as `1;2;3;`.
+pub fn parse_instance_id_list(
+    raw_list: &Value,
+    field: &'static str,
+) -> Result<Vec<i64>, AccessFieldsError> {
     if let Value::Null = raw_list {
         return Ok(vec![]);
     }
@@ -108,6 +320,23 @@ fn get_instance_id_list_field(
         .collect()
 }
 
+/// The value of a list of `unitInstanceID`s, the way
+/// [parse_instance_id_list] reads it.
+pub fn instance_id_list_value(ids: &[i64]) -> Value {
+    if ids.is_empty() {
+        return Value::Null;
+    }
+
+    Value::String(ids.iter().map(|id| format!("{id};")).collect())
+}
+
+fn get_instance_id_list_field(
+    unit: &UnitRef,
+    field: &'static str,
+) -> Result<Vec<i64>, AccessFieldsError> {
+    parse_instance_id_list(get_unit_field(unit, field)?, field)
+}
+
 fn parse_sam_radar(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
     assert_eq!(unit.unit_type(), Some(crate::UnitType::SAMRadar));
 
@@ -158,6 +387,116 @@ fn parse_irmd(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
     Ok(Fields::IRMD { engage_enemies })
 }
 
+fn parse_aircraft(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
+    assert_eq!(unit.unit_type(), Some(crate::UnitType::Aircraft));
+
+    let engage_enemies = get_engage_enemies(unit)?;
+
+    let default_behavior = get_field_with(unit, "defaultBehavior", parse_string)?;
+    let initial_speed = get_field_with(unit, "initialSpeed", parse_float)?;
+    let default_nav_speed = get_field_with(unit, "defaultNavSpeed", parse_float)?;
+    let default_orbit_point = get_field_with(unit, "defaultOrbitPoint", parse_optional_id)?;
+    let default_path = get_field_with(unit, "defaultPath", parse_optional_id)?;
+    let orbit_altitude = get_field_with(unit, "orbitAltitude", parse_float)?;
+    let fuel = get_field_with(unit, "fuel", parse_float)?;
+    let auto_refuel = get_field_with(unit, "autoRefuel", parse_bool)?;
+    let auto_rtb = get_field_with(unit, "autoRTB", parse_bool)?;
+    let rtb_destination = get_field_with(unit, "rtbDestination", parse_optional_string)?;
+    let parked_start_mode = get_field_with(unit, "parkedStartMode", parse_string)?;
+
+    let default_radar_enabled = get_optional_field_with(unit, "defaultRadarEnabled", parse_bool)?;
+    let allow_jamming_at_will = get_optional_field_with(unit, "allowJammingAtWill", parse_bool)?;
+    let voice_profile =
+        get_optional_field_with(unit, "voiceProfile", parse_optional_string)?.flatten();
+    let player_commands_mode = get_optional_field_with(unit, "playerCommandsMode", parse_string)?;
+
+    Ok(Fields::Aircraft {
+        engage_enemies,
+        default_behavior,
+        initial_speed,
+        default_nav_speed,
+        default_orbit_point,
+        default_path,
+        orbit_altitude,
+        fuel,
+        auto_refuel,
+        auto_rtb,
+        rtb_destination,
+        parked_start_mode,
+        default_radar_enabled,
+        allow_jamming_at_will,
+        voice_profile,
+        player_commands_mode,
+    })
+}
+
+fn parse_ground_unit(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
+    assert_eq!(unit.unit_type(), Some(crate::UnitType::GroundUnit));
+
+    let engage_enemies = get_engage_enemies(unit)?;
+
+    let behavior = get_field_with(unit, "behavior", parse_string)?;
+    let waypoint = get_field_with(unit, "waypoint", parse_optional_id)?;
+    let move_speed = get_optional_field_with(unit, "moveSpeed", parse_string)?;
+    let default_path = get_field_with(unit, "defaultPath", parse_optional_id)?;
+    let stop_to_engage = get_optional_field_with(unit, "stopToEngage", parse_bool)?;
+    let unit_group = get_field_with(unit, "unitGroup", parse_optional_string)?;
+    let detection_mode = get_field_with(unit, "detectionMode", parse_string)?;
+
+    Ok(Fields::GroundUnit {
+        engage_enemies,
+        behavior,
+        waypoint,
+        move_speed,
+        default_path,
+        stop_to_engage,
+        unit_group,
+        detection_mode,
+    })
+}
+
+fn parse_ship(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
+    assert_eq!(unit.unit_type(), Some(crate::UnitType::Ship));
+
+    let engage_enemies = get_engage_enemies(unit)?;
+
+    let default_behavior = get_field_with(unit, "defaultBehavior", parse_string)?;
+    let default_waypoint = get_field_with(unit, "defaultWaypoint", parse_optional_id)?;
+    let default_path = get_field_with(unit, "defaultPath", parse_optional_id)?;
+    let hull_number = get_optional_field_with(unit, "hullNumber", parse_number)?;
+
+    Ok(Fields::Ship {
+        engage_enemies,
+        default_behavior,
+        default_waypoint,
+        default_path,
+        hull_number,
+    })
+}
+
+fn parse_carrier(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
+    assert_eq!(unit.unit_type(), Some(crate::UnitType::Carrier));
+
+    let engage_enemies = get_engage_enemies(unit)?;
+
+    let default_behavior = get_field_with(unit, "defaultBehavior", parse_string)?;
+    let default_waypoint = get_field_with(unit, "defaultWaypoint", parse_optional_id)?;
+    let default_path = get_field_with(unit, "defaultPath", parse_optional_id)?;
+    let hull_number = get_optional_field_with(unit, "hullNumber", parse_number)?;
+    let lso_freq = get_optional_field_with(unit, "lsoFreq", parse_float)?;
+    let carrier_spawns = get_field_with(unit, "carrierSpawns", |v, _| Ok(format_value(v)))?;
+
+    Ok(Fields::Carrier {
+        engage_enemies,
+        default_behavior,
+        default_waypoint,
+        default_path,
+        hull_number,
+        lso_freq,
+        carrier_spawns,
+    })
+}
+
 pub fn access_fields(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
     let Some(t) = unit.unit_type() else {
         return Err(AccessFieldsError::UnitMissingType);
@@ -169,6 +508,10 @@ pub fn access_fields(unit: &UnitRef) -> Result<Fields, AccessFieldsError> {
         crate::UnitType::SAMLauncher => parse_sam_launcher(unit),
         crate::UnitType::MissileWarning => parse_missile_warning(unit),
         crate::UnitType::IRMD => parse_irmd(unit),
+        crate::UnitType::Aircraft => parse_aircraft(unit),
+        crate::UnitType::GroundUnit => parse_ground_unit(unit),
+        crate::UnitType::Ship => parse_ship(unit),
+        crate::UnitType::Carrier => parse_carrier(unit),
         _ => Err(AccessFieldsError::InvalidUnitType),
     }
 }
@@ -1,10 +1,14 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use vts_parsing::{Node, Value};
 
 include!(concat!(env!("OUT_DIR"), "/units.rs"));
 
+pub mod campaign;
+pub mod edit;
 pub mod fields;
+pub mod write;
 
 pub struct UnitRef<'a> {
     id: i64,
@@ -13,6 +17,7 @@ pub struct UnitRef<'a> {
     unit_type: Option<UnitType>,
 
     name: String,
+    position: Option<[f64; 3]>,
     fields: &'a Node,
 }
 
@@ -28,14 +33,18 @@ pub enum ToUnitRefError {
     MissingUnitFields,
 }
 
-impl<'a> TryFrom<&'a Node> for UnitRef<'a> {
-    type Error = ToUnitRefError;
+/// The values every `UnitSpawner` has, whether we know the unit or not.
+struct SpawnerValues<'a> {
+    id: i64,
+    name: String,
+    position: Option<[f64; 3]>,
+    fields: &'a Node,
+}
 
-    fn try_from(node: &'a Node) -> Result<UnitRef<'a>, ToUnitRefError> {
-        let unit = get_unit(node).ok_or(ToUnitRefError::NotValidUnit)?;
-        let unit_faction = get_unit_faction(&unit);
-        let unit_type = get_unit_type(&unit);
+impl<'a> TryFrom<&'a Node> for SpawnerValues<'a> {
+    type Error = ToUnitRefError;
 
+    fn try_from(node: &'a Node) -> Result<SpawnerValues<'a>, ToUnitRefError> {
         let id = node
             .values
             .get("unitInstanceID")
@@ -57,10 +66,38 @@ impl<'a> TryFrom<&'a Node> for UnitRef<'a> {
             .as_string()
             .ok_or(ToUnitRefError::InvalidUnitName)?
             .to_string();
+        let position = node
+            .values
+            .get("globalPosition")
+            .and_then(|v| v.as_vector());
         let fields = node
             .get_node("UnitFields")
             .ok_or(ToUnitRefError::MissingUnitFields)?;
 
+        Ok(SpawnerValues {
+            id,
+            name,
+            position,
+            fields,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a Node> for UnitRef<'a> {
+    type Error = ToUnitRefError;
+
+    fn try_from(node: &'a Node) -> Result<UnitRef<'a>, ToUnitRefError> {
+        let unit = get_unit(node).ok_or(ToUnitRefError::NotValidUnit)?;
+        let unit_faction = get_unit_faction(&unit);
+        let unit_type = get_unit_type(&unit);
+
+        let SpawnerValues {
+            id,
+            name,
+            position,
+            fields,
+        } = node.try_into()?;
+
         Ok(UnitRef {
             id,
             unit,
@@ -68,6 +105,7 @@ impl<'a> TryFrom<&'a Node> for UnitRef<'a> {
             unit_type,
 
             name,
+            position,
             fields,
         })
     }
@@ -90,6 +128,16 @@ impl<'a> UnitRef<'a> {
         &self.name
     }
 
+    /// The unit's `globalPosition`, if it has a valid one.
+    pub fn position(&self) -> Option<[f64; 3]> {
+        self.position
+    }
+
+    /// The raw `UnitFields` node.
+    pub fn fields(&self) -> &'a Node {
+        self.fields
+    }
+
     pub fn get_field(&self, k: &str) -> Option<&Value> {
         self.fields.values.get(k)
     }
@@ -99,8 +147,99 @@ impl<'a> UnitRef<'a> {
     }
 }
 
+/// A unit whose `unitID` isn't in [Unit], e.g. one added in a newer version
+/// of the game, kept by [Scenario::from_node_lenient].
+pub struct UnknownUnit<'a> {
+    id: i64,
+    unit_id: String,
+    name: String,
+    position: Option<[f64; 3]>,
+    fields: &'a Node,
+}
+
+impl<'a> TryFrom<&'a Node> for UnknownUnit<'a> {
+    type Error = ToUnitRefError;
+
+    fn try_from(node: &'a Node) -> Result<UnknownUnit<'a>, ToUnitRefError> {
+        let unit_id = node
+            .values
+            .get("unitID")
+            .and_then(|v| v.as_string())
+            .ok_or(ToUnitRefError::NotValidUnit)?
+            .to_string();
+
+        let SpawnerValues {
+            id,
+            name,
+            position,
+            fields,
+        } = node.try_into()?;
+
+        Ok(UnknownUnit {
+            id,
+            unit_id,
+            name,
+            position,
+            fields,
+        })
+    }
+}
+
+impl<'a> UnknownUnit<'a> {
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// The raw `unitID`, e.g. `AMWSTruck Variant`.
+    pub fn unit_id(&self) -> &str {
+        &self.unit_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn position(&self) -> Option<[f64; 3]> {
+        self.position
+    }
+
+    /// The raw `UnitFields` node.
+    pub fn fields(&self) -> &'a Node {
+        self.fields
+    }
+
+    pub fn get_field(&self, k: &str) -> Option<&Value> {
+        self.fields.values.get(k)
+    }
+}
+
+/// Either kind of unit, for when both can show up.
+pub enum AnyUnit<'a> {
+    Known(UnitRef<'a>),
+    Unknown(UnknownUnit<'a>),
+}
+
+impl<'a> AnyUnit<'a> {
+    pub fn id(&self) -> i64 {
+        match self {
+            AnyUnit::Known(u) => u.id(),
+            AnyUnit::Unknown(u) => u.id(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            AnyUnit::Known(u) => u.name(),
+            AnyUnit::Unknown(u) => u.name(),
+        }
+    }
+}
+
 pub struct Scenario<'a> {
+    node: &'a Node,
     units: HashMap<i64, UnitRef<'a>>,
+    unknown_units: HashMap<i64, UnknownUnit<'a>>,
+    duplicates: Vec<AnyUnit<'a>>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -111,7 +250,7 @@ pub enum ToScenarioError {
     UnitsMissing,
     #[error("Unit could not be converted to UnitRef")]
     InvalidUnit {
-        unit: Node,
+        unit: Box<Node>,
         #[source]
         reason: ToUnitRefError,
     },
@@ -121,31 +260,109 @@ impl<'a> TryFrom<&'a Node> for Scenario<'a> {
     type Error = ToScenarioError;
 
     fn try_from(node: &'a Node) -> Result<Scenario<'a>, ToScenarioError> {
+        Scenario::from_node(node, false)
+    }
+}
+
+impl<'a> Scenario<'a> {
+    /// Like [Scenario::try_from], but units with an unknown `unitID` end up
+    /// in [Scenario::unknown_units] instead of failing the conversion.
+    pub fn from_node_lenient(node: &'a Node) -> Result<Scenario<'a>, ToScenarioError> {
+        Scenario::from_node(node, true)
+    }
+
+    fn from_node(node: &'a Node, lenient: bool) -> Result<Scenario<'a>, ToScenarioError> {
         if node.name != "CustomScenario" {
             return Err(ToScenarioError::NotCustomScenario);
         }
 
-        let units = node
+        let units_node = node
             .get_node("UNITS")
             .ok_or(ToScenarioError::UnitsMissing)?;
 
-        let units = units
-            .nodes()
-            .map(|n| {
-                let unit_ref = UnitRef::try_from(n).map_err(|e| (n.clone(), e))?;
-                let key = unit_ref.id();
+        let mut units = HashMap::new();
+        let mut unknown_units = HashMap::new();
+        let mut duplicates = Vec::new();
+
+        for n in units_node.nodes() {
+            let invalid = |reason| ToScenarioError::InvalidUnit {
+                unit: Box::new(n.clone()),
+                reason,
+            };
+
+            let unit = match UnitRef::try_from(n) {
+                Ok(unit_ref) => AnyUnit::Known(unit_ref),
+                Err(ToUnitRefError::NotValidUnit) if lenient => {
+                    AnyUnit::Unknown(UnknownUnit::try_from(n).map_err(invalid)?)
+                }
+                Err(e) => return Err(invalid(e)),
+            };
 
-                Ok((key, unit_ref))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()
-            .map_err(|(n, e)| ToScenarioError::InvalidUnit { unit: n, reason: e })?;
+            let id = unit.id();
+            if units.contains_key(&id) || unknown_units.contains_key(&id) {
+                duplicates.push(unit);
+                continue;
+            }
 
-        Ok(Scenario { units })
+            match unit {
+                AnyUnit::Known(u) => {
+                    units.insert(id, u);
+                }
+                AnyUnit::Unknown(u) => {
+                    unknown_units.insert(id, u);
+                }
+            }
+        }
+
+        Ok(Scenario {
+            node,
+            units,
+            unknown_units,
+            duplicates,
+        })
     }
-}
 
-impl<'a> Scenario<'a> {
-    pub fn units(&self) -> &HashMap<i64, UnitRef> {
+    pub fn units(&self) -> &HashMap<i64, UnitRef<'a>> {
         &self.units
     }
+
+    /// Units with an unknown `unitID`, always empty unless loaded with
+    /// [Scenario::from_node_lenient].
+    pub fn unknown_units(&self) -> &HashMap<i64, UnknownUnit<'a>> {
+        &self.unknown_units
+    }
+
+    /// Units whose `unitInstanceID` was already taken by an earlier unit in
+    /// the scenario, which is the one in [Scenario::units] or
+    /// [Scenario::unknown_units].
+    pub fn duplicates(&self) -> &[AnyUnit<'a>] {
+        &self.duplicates
+    }
+
+    /// `scenarioID`, which is also the name of the scenario's directory.
+    pub fn scenario_id(&self) -> Option<Cow<'_, str>> {
+        self.get_value("scenarioID")?.as_string_cvt()
+    }
+
+    /// `campaignID`, `None` if the scenario isn't part of a campaign.
+    pub fn campaign_id(&self) -> Option<Cow<'_, str>> {
+        self.get_value("campaignID")?
+            .as_string_cvt()
+            .filter(|id| !id.is_empty())
+    }
+
+    /// `campaignOrderIdx`, the position of the scenario in its campaign.
+    pub fn campaign_order_idx(&self) -> Option<i64> {
+        self.get_value("campaignOrderIdx")?.as_number()
+    }
+
+    /// The raw `CustomScenario` node.
+    pub fn node(&self) -> &'a Node {
+        self.node
+    }
+
+    /// A top-level value of the scenario, e.g. `scenarioDescription`.
+    pub fn get_value(&self, k: &str) -> Option<&Value> {
+        self.node.values.get(k)
+    }
 }
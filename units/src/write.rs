@@ -0,0 +1,206 @@
+//! Writing a [Node] back to `.vts` text, the way the game does.
+//!
+//! For a scenario saved by the game, parsing and writing it again gives the
+//! same bytes. [vts_parsing] doesn't keep the text of values, so floats are
+//! formatted like the game formats them: values with up to 7 significant
+//! digits are assumed to be single precision (C# `G`), anything longer double
+//! precision (C# `G17`).
+
+use std::fmt::Write;
+
+use vts_parsing::{Node, Value};
+
+/// Significant digits of a C# `float`, written with `G`.
+const FLOAT_DIGITS: usize = 7;
+/// Significant digits of a C# `double`, written with `G17`.
+const DOUBLE_DIGITS: usize = 17;
+/// Enough digits to write any `f64` exactly.
+const EXACT_DIGITS: usize = 767;
+
+/// Writes a positive number like C#'s `G` format with `precision`
+/// significant digits, given its decimal digits and scientific exponent.
+fn write_general(out: &mut String, digits: &str, exponent: i32, precision: usize) {
+    let digits = match digits.trim_end_matches('0') {
+        "" => "0",
+        digits => digits,
+    };
+
+    if digits == "0" {
+        out.push('0');
+    } else if exponent < -5 || exponent >= precision as i32 {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        let sign = if exponent < 0 { '-' } else { '+' };
+        write!(out, "E{sign}{:02}", exponent.abs()).unwrap();
+    } else if exponent < 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat_n('0', (-exponent - 1) as usize));
+        out.push_str(digits);
+    } else {
+        let integer = exponent as usize + 1;
+        if digits.len() <= integer {
+            out.push_str(digits);
+            out.extend(std::iter::repeat_n('0', integer - digits.len()));
+        } else {
+            out.push_str(&digits[..integer]);
+            out.push('.');
+            out.push_str(&digits[integer..]);
+        }
+    }
+}
+
+/// Splits Rust's `{:e}` formatting into digits and exponent.
+fn split_scientific(formatted: &str) -> (String, i32) {
+    let (mantissa, exponent) = formatted
+        .split_once('e')
+        .expect("scientific formatting has an exponent");
+
+    (
+        mantissa.replace('.', ""),
+        exponent.parse().expect("exponent is a number"),
+    )
+}
+
+/// The first `precision` digits of `v`, rounded half away from zero like C#
+/// does, where Rust's formatting rounds half to even.
+fn round_digits(v: f64, precision: usize) -> (String, i32) {
+    let (exact, mut exponent) = split_scientific(&format!("{:.*e}", EXACT_DIGITS, v));
+    let mut digits = exact.as_bytes()[..precision].to_vec();
+
+    if exact.as_bytes()[precision] >= b'5' {
+        let carried = digits.iter_mut().rev().all(|d| {
+            if *d == b'9' {
+                *d = b'0';
+                true
+            } else {
+                *d += 1;
+                false
+            }
+        });
+
+        if carried {
+            digits.insert(0, b'1');
+            digits.pop();
+            exponent += 1;
+        }
+    }
+
+    (
+        String::from_utf8(digits).expect("digits are ascii"),
+        exponent,
+    )
+}
+
+fn write_float(out: &mut String, v: f64) {
+    if v.is_nan() {
+        out.push_str("NaN");
+        return;
+    }
+    if v.is_infinite() {
+        out.push_str(if v > 0.0 { "Infinity" } else { "-Infinity" });
+        return;
+    }
+
+    if v < 0.0 {
+        out.push('-');
+    }
+
+    // The shortest digits which parse back to `v`.
+    let (digits, exponent) = split_scientific(&format!("{:e}", v.abs()));
+
+    if digits.len() <= FLOAT_DIGITS {
+        write_general(out, &digits, exponent, FLOAT_DIGITS);
+    } else {
+        let (digits, exponent) = round_digits(v.abs(), DOUBLE_DIGITS);
+        write_general(out, &digits, exponent, DOUBLE_DIGITS);
+    }
+}
+
+fn write_vector(out: &mut String, v: &[f64; 3]) {
+    out.push('(');
+    for (idx, component) in v.iter().enumerate() {
+        if idx > 0 {
+            out.push_str(", ");
+        }
+        write_float(out, *component);
+    }
+    out.push(')');
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Number(n) => write!(out, "{n}").unwrap(),
+        Value::Float(f) => write_float(out, *f),
+        Value::Boolean(true) => out.push_str("True"),
+        Value::Boolean(false) => out.push_str("False"),
+        Value::Vector(v) => write_vector(out, v),
+        Value::VectorGroup(vectors) => {
+            for v in vectors {
+                write_vector(out, v);
+                out.push(';');
+            }
+        }
+        Value::String(s) => out.push_str(s),
+        Value::Null => {}
+    }
+}
+
+/// A value as the game writes it, e.g. `True` or `1;2;3;`.
+pub fn format_value(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_node(out: &mut String, node: &Node, depth: usize) {
+    let indent = "\t".repeat(depth);
+
+    writeln!(out, "{indent}{}", node.name).unwrap();
+    writeln!(out, "{indent}{{").unwrap();
+
+    for (key, value) in &node.values {
+        write!(out, "{indent}\t{key} = ").unwrap();
+        write_value(out, value);
+        out.push('\n');
+    }
+
+    for child in &node.nodes {
+        write_node(out, child, depth + 1);
+    }
+
+    writeln!(out, "{indent}}}").unwrap();
+}
+
+/// The text of a `.vts` (or `.vtc`) file with `node` at the top.
+///
+/// Not [vts_parsing::unparse()], which writes floats with Rust's
+/// `Display`, e.g. `0.000009486375` where the game writes `9.486375E-06`, so
+/// saving an unchanged scenario would rewrite most of its positions.
+pub fn to_vts(node: &Node) -> String {
+    let mut out = String::new();
+    write_node(&mut out, node, 0);
+
+    // The game ends its files with an empty line, using Windows line endings.
+    out.push_str("\r\n");
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use vts_parsing::parse::try_parse;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let source = include_str!("../../UnitIDTesting.vts");
+        let node = try_parse(source).unwrap();
+
+        assert!(to_vts(&node) == source, "written scenario differs");
+    }
+}